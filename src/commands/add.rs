@@ -1,4 +1,5 @@
-use crate::{core::*, parse::*, *};
+use crate::select::*;
+use crate::{core::*, parse::*, validate::*, *};
 use yatt_orm::statement::*;
 
 pub(crate) fn exec<T: DBRoot, P: Printer>(
@@ -6,19 +7,19 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
   args: &ArgMatches,
 ) -> CliResult<()> {
   let duration = parse_duration(args.value_of("DURATION").unwrap())?;
-  let task: Option<usize> = if let Some(t) = args.value_of("task") {
-    Some(t.parse().map_err(|e| CliError::wrap(Box::new(e)))?)
+  let task = if let Some(t) = args.value_of("task") {
+    Some(select_task(ctx.db, t)?)
   } else {
     None
   };
 
-  let (node, interval) = if let Some(id) = task {
-    let node: Node = ctx.db.get_by_id(id)?;
+  let (node, interval) = if let Some(node) = task {
     let mut interval: Vec<Interval> = ctx.db.get_by_statement(
       filter(and(
-        eq(Interval::id_n(), node.id),
-        eq(Interval::deleted_n(), 0),
+        eq(Interval::node_id_n(), node.id),
+        ne(Interval::deleted_n(), 1),
       ))
+      .sort(Interval::begin_n(), SortDir::Descend)
       .limit(1),
     )?;
     if interval.is_empty() {
@@ -54,6 +55,8 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
         deleted: false,
         id: 0,
         node_id: Some(node.id),
+        pomodoro: false,
+        utc_offset: Some(local_offset(begin)),
       }
     }
     Some(interval) => {
@@ -67,28 +70,14 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
             deleted: false,
             id: interval.id,
             node_id: interval.node_id,
+            pomodoro: interval.pomodoro,
+            utc_offset: interval.utc_offset,
           }
         } else {
           let rest = duration - (now - end);
           let end = now;
           let begin: DateTime<Utc> = interval.begin - rest;
 
-          if !ctx
-            .db
-            .get_by_statement::<Interval>(
-              filter(and(
-                gt(Interval::end_n(), begin),
-                eq(Interval::deleted_n(), 0),
-              ))
-              .limit(1),
-            )?
-            .is_empty()
-          {
-            return Err(CliError::Cmd {
-              message: "Given interval is too long".into(),
-            });
-          }
-
           Interval {
             begin,
             end: Some(end),
@@ -96,26 +85,12 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
             deleted: false,
             id: interval.id,
             node_id: interval.node_id,
+            pomodoro: interval.pomodoro,
+            utc_offset: interval.utc_offset,
           }
         }
       } else {
         let begin: DateTime<Utc> = interval.begin - duration;
-        if !ctx
-          .db
-          .get_by_statement::<Interval>(
-            filter(and(
-              gt(Interval::end_n(), begin),
-              eq(Interval::deleted_n(), 0),
-            ))
-            .limit(1),
-          )?
-          .is_empty()
-        {
-          return Err(CliError::Cmd {
-            message: "Given interval is too long".into(),
-          });
-        }
-
         Interval {
           begin,
           end: interval.end,
@@ -123,11 +98,15 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
           deleted: false,
           id: interval.id,
           node_id: interval.node_id,
+          pomodoro: interval.pomodoro,
+          utc_offset: interval.utc_offset,
         }
       }
     }
   };
 
+  check_overlaps(ctx.db, &interval)?;
+
   let cmd_text = if interval.id > 0 {
     &"Interval extended"
   } else {
@@ -163,14 +142,15 @@ pub fn register<'a>(app: App<'a, 'a>) -> App {
       .setting(AppSettings::ArgRequiredElseHelp)
       .arg(
         Arg::with_name("DURATION")
-          .help("Time to add to interval")
+          .help("Time to add to interval (e.g. 30m, 1h30m, 1.5h or 01:30)")
           .required(true),
       )
       .arg(
         Arg::with_name("task")
           .short("t")
           .long("task")
-          .help("Task id")
+          .help(TASK_SELECTOR_HELP)
+          .allow_hyphen_values(true)
           .takes_value(true)
           .multiple(true),
       ),
@@ -0,0 +1,112 @@
+use super::template::collect_subtree;
+use crate::core::*;
+use crate::pick::*;
+use crate::select::*;
+use crate::*;
+use yatt_orm::statement::*;
+use yatt_orm::FieldVal;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let node = select_task(
+    ctx.db,
+    &value_or_pick(ctx, args, "ID", PickKind::Task)?,
+  )?;
+  let parent = args.value_of("PARENT_ID").unwrap();
+  let mut path = if parent.trim() == "0" {
+    Vec::new()
+  } else {
+    ctx.db.ancestors(select_task(ctx.db, parent)?.id)?
+  };
+  let parent_id = path.last().map(|n| n.id);
+  let label =
+    args.value_of("label").unwrap_or(&node.label).to_string();
+
+  // Deleted siblings are checked too, as labels should stay
+  // unique for paths to be resolved.
+  let siblings: Vec<Node> =
+    ctx.db.get_by_filter(if let Some(parent_id) = parent_id {
+      eq(Node::parent_id_n(), parent_id)
+    } else {
+      eq(Node::parent_id_n(), FieldVal::Null)
+    })?;
+  if let Some(sibling) = siblings.iter().find(|n| n.label == label) {
+    return Err(CliError::Cmd {
+      message: format!(
+        "Task \"{}\" {} there, use --label to set another label",
+        label,
+        if sibling.deleted {
+          "was deleted"
+        } else {
+          "already exists"
+        }
+      ),
+    });
+  }
+
+  let mut nodes = Vec::new();
+  collect_subtree(ctx.db, node, 0, &mut nodes)?;
+
+  // Ids of the last created node on each level.
+  let mut parents: Vec<Option<usize>> = vec![parent_id];
+  let mut root = None;
+  for (depth, node) in nodes.iter() {
+    parents.truncate(depth + 1);
+    let mut copy = Node {
+      parent_id: parents[*depth],
+      label: node.label.to_owned(),
+      tags: node.tags.to_owned(),
+      estimate: node.estimate,
+      rate: node.rate,
+      currency: node.currency.to_owned(),
+      ..Node::default()
+    };
+    if *depth == 0 {
+      copy.label = label.to_owned();
+    }
+    copy.id = ctx.db.save(&copy)?;
+    parents.push(Some(copy.id));
+    if root.is_none() {
+      root = Some(copy);
+    }
+  }
+  path.push(root.unwrap());
+
+  ctx.printer.node_cmd(&NodeCmdData {
+    cmd_text: &format!("Cloned with {} task(s).", nodes.len()),
+    node: NodeData {
+      title: NodeData::default_title(),
+      node: &path,
+    },
+  });
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("clone")
+      .setting(AppSettings::AllowMissingPositional)
+      .about(
+        "Copies task with all subtasks to new parent\n\
+        Tags, estimates and rates are copied, intervals are not.",
+      )
+      .setting(AppSettings::ArgRequiredElseHelp)
+      .setting(AppSettings::AllowNegativeNumbers)
+      .arg(Arg::with_name("ID").help(TASK_SELECTOR_HELP))
+      .arg(
+        Arg::with_name("PARENT_ID")
+          .help("New parent task selector, or 0 to make copy a root")
+          .required(true),
+      )
+      .arg(
+        Arg::with_name("label")
+          .short("l")
+          .long("label")
+          .help("Label of the copy")
+          .takes_value(true),
+      ),
+  )
+}
@@ -1,49 +1,18 @@
 use crate::core::*;
+use crate::pick::*;
+use crate::select::*;
 use crate::*;
 use crossterm_input::input;
-use std::convert::TryInto;
-use yatt_orm::{statement::*, DBError, FieldVal};
 
 pub(crate) fn exec<T: DBRoot, P: Printer>(
   ctx: &AppContext<T, P>,
   args: &ArgMatches,
 ) -> CliResult<()> {
   let no_prompt = args.is_present("yes");
-  let id: i64 =
-    args.value_of("ID").unwrap().parse().map_err(|_| {
-      CliError::Parse {
-        message: "Unable to parse interval ID".into(),
-      }
-    })?;
-
-  let mut interval = if id < 0 {
-    let offset: usize = (-id).try_into().unwrap();
-    let intervals: Vec<Interval> = ctx.db.get_by_statement(
-      filter(and(
-        ne(Interval::deleted_n(), 1),
-        ne(Interval::end_n(), FieldVal::Null),
-      ))
-      .sort(Interval::begin_n(), SortDir::Descend)
-      .limit(offset),
-    )?;
-    if intervals.len() < offset {
-      return Err(CliError::Cmd {
-        message: "There is no interval with given offset".into(),
-      });
-    }
-    intervals.last().unwrap().to_owned()
-  } else {
-    let id: usize = id.try_into().unwrap();
-    ctx.db.get_by_id::<Interval>(id).map_err(|source| {
-      if let DBError::IsEmpty { .. } = source {
-        return CliError::Cmd {
-          message: "There is no interval with given ID".into(),
-        };
-      }
-
-      CliError::DB { source }
-    })?
-  };
+  let mut interval = select_interval(
+    ctx.db,
+    &value_or_pick(ctx, args, "ID", PickKind::Interval)?,
+  )?;
 
   let task: Node = ctx
     .db
@@ -104,8 +73,7 @@ pub fn register<'a>(app: App<'a, 'a>) -> App {
       .about("Deletes an interval")
       .arg(
         Arg::with_name("ID")
-          .help("[ID] or -[offset] from NOW (starting with -1)")
-          .required(true),
+          .help("[ID] or -[offset] from NOW (starting with -1)"),
       ),
   )
 }
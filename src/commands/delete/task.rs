@@ -1,4 +1,6 @@
 use crate::core::*;
+use crate::pick::*;
+use crate::select::*;
 use crate::*;
 use crossterm_input::input;
 
@@ -7,21 +9,13 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
   args: &ArgMatches,
 ) -> CliResult<()> {
   let no_prompt = args.is_present("yes");
-  let id: usize =
-    args.value_of("ID").unwrap().parse().map_err(|_| {
-      CliError::Parse {
-        message: "Unable to parse task ID".into(),
-      }
-    })?;
-  let mut task = ctx.db.get_by_id::<Node>(id)?;
+  let mut task = select_task(
+    ctx.db,
+    &value_or_pick(ctx, args, "ID", PickKind::Task)?,
+  )?;
+  let id = task.id;
   let is_group = ctx.db.has_children(task.id)?;
 
-  if task.deleted {
-    return Err(CliError::Cmd {
-      message: "Task already deleted".into(),
-    });
-  }
-
   if let Some((node, _)) = ctx.db.cur_running()? {
     if node.id == id {
       return Err(CliError::Cmd {
@@ -85,6 +79,6 @@ pub fn register<'a>(app: App<'a, 'a>) -> App {
     SubCommand::with_name("task")
       .setting(AppSettings::AllowNegativeNumbers)
       .about("Deletes a task")
-      .arg(Arg::with_name("ID").help("Task id").required(true)),
+      .arg(Arg::with_name("ID").help(TASK_SELECTOR_HELP)),
   )
 }
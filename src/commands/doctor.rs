@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+
+use crate::core::*;
+use crate::*;
+use crossterm_input::input;
+use yatt_orm::statement::*;
+use yatt_orm::FieldVal;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let doctor = Doctor {
+    ctx,
+    fix: args.is_present("fix"),
+    no_prompt: args.is_present("yes"),
+  };
+
+  let found = doctor.check_running()?
+    + doctor.check_orphans()?
+    + doctor.check_overlaps()?
+    + doctor.check_duplicates()?;
+
+  if found == 0 {
+    ctx.printer.plain("No problems found.");
+  } else if !doctor.fix {
+    ctx.printer.plain(&format!(
+      "Found {} problem(s), run with --fix to repair them.",
+      found
+    ));
+  }
+
+  Ok(())
+}
+
+struct Doctor<'a, 'b, T: DBRoot, P: Printer> {
+  ctx: &'a AppContext<'b, T, P>,
+  fix: bool,
+  no_prompt: bool,
+}
+
+impl<T: DBRoot, P: Printer> Doctor<'_, '_, T, P> {
+  /// More than one interval without end.
+  fn check_running(&self) -> CliResult<usize> {
+    let mut running: Vec<Interval> = self.ctx.db.get_by_statement(
+      filter(and(
+        eq(Interval::end_n(), FieldVal::Null),
+        ne(Interval::deleted_n(), 1),
+      ))
+      .sort(Interval::begin_n(), SortDir::Ascend),
+    )?;
+    if running.len() < 2 {
+      return Ok(0);
+    }
+
+    let found = running.len() - 1;
+    let last_begin = running.last().unwrap().begin;
+    let next_begins: Vec<DateTime<Utc>> =
+      running.iter().skip(1).map(|i| i.begin).collect();
+    for (interval, next) in running.iter_mut().zip(next_begins) {
+      self.ctx.printer.warning(&format!(
+        "Interval {} is running along with interval started at {}",
+        interval.id,
+        format::format_datetime(&last_begin, &self.ctx.tz)
+      ));
+      self.print_interval(interval);
+      if !self.confirm("Stop it, when the next one started?")? {
+        continue;
+      }
+      if next > interval.begin {
+        interval.end = Some(next);
+      } else {
+        interval.deleted = true;
+      }
+      self.ctx.db.save(interval)?;
+    }
+
+    Ok(found)
+  }
+
+  /// Intervals of missing or deleted tasks.
+  fn check_orphans(&self) -> CliResult<usize> {
+    let nodes: HashMap<usize, Node> = self
+      .ctx
+      .db
+      .get_all::<Node>()?
+      .into_iter()
+      .map(|n| (n.id, n))
+      .collect();
+    let intervals: Vec<Interval> =
+      self.ctx.db.get_by_filter(ne(Interval::deleted_n(), 1))?;
+
+    let mut found = 0;
+    for mut interval in intervals {
+      let message = match interval.node_id.map(|id| nodes.get(&id)) {
+        None => "has no task".to_string(),
+        Some(None) => format!(
+          "refers to missing task {}",
+          interval.node_id.unwrap()
+        ),
+        Some(Some(node)) if node.deleted => {
+          format!("refers to deleted task {}", node.id)
+        }
+        _ => continue,
+      };
+      found += 1;
+      self
+        .ctx
+        .printer
+        .warning(&format!("Interval {} {}", interval.id, message));
+      self.print_interval(&interval);
+      if !self.confirm("Delete the interval?")? {
+        continue;
+      }
+      interval.deleted = true;
+      self.ctx.db.save(&interval)?;
+    }
+
+    Ok(found)
+  }
+
+  /// Intervals which intersect with each other.
+  fn check_overlaps(&self) -> CliResult<usize> {
+    let now = Utc::now();
+    let intervals: Vec<Interval> = self.ctx.db.get_by_statement(
+      filter(ne(Interval::deleted_n(), 1))
+        .sort(Interval::begin_n(), SortDir::Ascend)
+        .sort(Interval::id_n(), SortDir::Ascend),
+    )?;
+
+    let mut found = 0;
+    let mut prev: Option<Interval> = None;
+    for mut next in intervals {
+      let mut cur = match prev {
+        Some(cur) if cur.end.unwrap_or(now) > next.begin => cur,
+        _ => {
+          prev = Some(next);
+          continue;
+        }
+      };
+
+      found += 1;
+      self.ctx.printer.warning(&format!(
+        "Intervals {} and {} overlap",
+        cur.id, next.id
+      ));
+      self.print_interval(&cur);
+      self.print_interval(&next);
+
+      let cur_end = cur.end.unwrap_or(now);
+      let next_end = next.end.unwrap_or(now);
+      if cur.begin < next.begin {
+        if self.confirm(&format!(
+          "Stop interval {} at {}?",
+          cur.id,
+          format::format_datetime(&next.begin, &self.ctx.tz)
+        ))? {
+          cur.end = Some(next.begin);
+          self.ctx.db.save(&cur)?;
+          prev = Some(next);
+          continue;
+        }
+      } else if cur_end >= next_end {
+        if self.confirm(&format!(
+          "Delete interval {}, which lies within interval {}?",
+          next.id, cur.id
+        ))? {
+          next.deleted = true;
+          self.ctx.db.save(&next)?;
+          continue;
+        }
+      } else if self.confirm(&format!(
+        "Start interval {} at {}?",
+        next.id,
+        format::format_datetime(&cur_end, &self.ctx.tz)
+      ))? {
+        next.begin = cur_end;
+        self.ctx.db.save(&next)?;
+        prev = Some(next);
+        continue;
+      }
+
+      if next_end > cur_end {
+        prev = Some(next);
+      }
+    }
+
+    Ok(found)
+  }
+
+  /// Sibling tasks with the same label.
+  fn check_duplicates(&self) -> CliResult<usize> {
+    let mut siblings: HashMap<(Option<usize>, String), Vec<Node>> =
+      HashMap::new();
+    for node in self.ctx.db.get_all::<Node>()? {
+      siblings
+        .entry((node.parent_id, node.label.to_owned()))
+        .or_default()
+        .push(node);
+    }
+    let mut duplicates: Vec<_> =
+      siblings.into_iter().filter(|(_, v)| v.len() > 1).collect();
+    duplicates.sort_by_key(|(_, v)| v.iter().map(|n| n.id).min());
+
+    let mut found = 0;
+    for (key, nodes) in duplicates {
+      // Nodes could be changed by previous fixes.
+      let mut nodes = nodes
+        .iter()
+        .map(|n| self.ctx.db.get_by_id::<Node>(n.id))
+        .collect::<Result<Vec<_>, _>>()?;
+      nodes.retain(|n| (n.parent_id, n.label.to_owned()) == key);
+      nodes.sort_by_key(|n| (n.deleted, n.id));
+      if nodes.len() < 2 {
+        continue;
+      }
+
+      found += 1;
+      let target = nodes.remove(0);
+      let path = self.ctx.db.ancestors(target.id)?;
+      self.ctx.printer.warning(&format!(
+        "Tasks {} have the same label and parent",
+        std::iter::once(&target)
+          .chain(nodes.iter())
+          .map(|n| n.id.to_string())
+          .collect::<Vec<_>>()
+          .join(", ")
+      ));
+      self.ctx.printer.node_cmd(&NodeCmdData {
+        cmd_text: "",
+        node: NodeData {
+          title: NodeData::default_title(),
+          node: &path,
+        },
+      });
+      if !self
+        .confirm(&format!("Merge them into task {}?", target.id))?
+      {
+        continue;
+      }
+      for node in nodes {
+        self.ctx.db.merge_node(&node, &target)?;
+        // Merged node stays in place, so it's label should
+        // become unique.
+        let mut node: Node = self.ctx.db.get_by_id(node.id)?;
+        node.label =
+          format!("{} (merged into {})", node.label, target.id);
+        self.ctx.db.save(&node)?;
+      }
+    }
+
+    Ok(found)
+  }
+
+  fn print_interval(&self, interval: &Interval) {
+    let task = interval
+      .node_id
+      .and_then(|id| self.ctx.db.ancestors(id).ok())
+      .unwrap_or_default();
+    self.ctx.printer.interval_cmd(&IntervalCmdData {
+      cmd_text: "",
+      interval: IntervalData {
+        interval,
+        task: &task,
+        title: &format!("Interval {}:", interval.id),
+      },
+    });
+  }
+
+  /// Returns true, if fix should be applied.
+  fn confirm(&self, question: &str) -> CliResult<bool> {
+    if !self.fix {
+      return Ok(false);
+    }
+    if self.no_prompt {
+      self.ctx.printer.plain(question);
+      return Ok(true);
+    }
+
+    self.ctx.printer.prompt(&format!("{} [y/n]", question));
+    let answer = input()
+      .read_char()
+      .map_err(|e| CliError::wrap(Box::new(e)))?;
+    Ok(answer == 'y')
+  }
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("doctor")
+      .about("Checks database for inconsistencies")
+      .arg(
+        Arg::with_name("fix")
+          .long("fix")
+          .help("Interactively repair found problems"),
+      )
+      .arg(
+        Arg::with_name("yes")
+          .short("y")
+          .requires("fix")
+          .help("Repair with no prompt"),
+      ),
+  )
+}
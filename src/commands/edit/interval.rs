@@ -0,0 +1,85 @@
+use crate::core::*;
+use crate::parse::*;
+use crate::pick::*;
+use crate::select::*;
+use crate::validate::*;
+use crate::*;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let mut interval = select_interval(
+    ctx.db,
+    &value_or_pick(ctx, args, "ID", PickKind::Interval)?,
+  )?;
+
+  if let Some(begin) = args.value_of("begin") {
+    interval.begin = parse_date_time(begin, &ctx.tz)?;
+  }
+  if let Some(end) = args.value_of("end") {
+    interval.end = Some(parse_date_time(end, &ctx.tz)?);
+  }
+  if let Some(task) = args.value_of("task") {
+    let node = select_task(ctx.db, task)?;
+    interval.node_id = Some(node.id);
+  }
+
+  check_interval(ctx.db, &interval)?;
+  ctx.db.save(&interval)?;
+
+  let task = ctx.db.ancestors(interval.node_id.unwrap())?;
+  ctx.printer.interval_cmd(&IntervalCmdData {
+    cmd_text: "Interval updated.",
+    interval: IntervalData {
+      interval: &interval,
+      task: &task,
+      title: IntervalData::default_title(),
+    },
+  });
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("interval")
+      .setting(AppSettings::AllowNegativeNumbers)
+      .setting(AppSettings::ArgRequiredElseHelp)
+      .about("Changes interval bounds or task")
+      .arg(
+        Arg::with_name("ID")
+          .help("[ID] or -[offset] from NOW (starting with -1)"),
+      )
+      .arg(
+        Arg::with_name("begin")
+          .short("b")
+          .long("begin")
+          .help("New begin date and time")
+          .takes_value(true)
+          .allow_hyphen_values(true),
+      )
+      .arg(
+        Arg::with_name("end")
+          .short("e")
+          .long("end")
+          .help("New end date and time")
+          .takes_value(true)
+          .allow_hyphen_values(true),
+      )
+      .arg(
+        Arg::with_name("task")
+          .short("t")
+          .long("task")
+          .help(TASK_SELECTOR_HELP)
+          .allow_hyphen_values(true)
+          .takes_value(true),
+      )
+      .group(
+        ArgGroup::with_name("changes")
+          .args(&["begin", "end", "task"])
+          .multiple(true)
+          .required(true),
+      ),
+  )
+}
@@ -0,0 +1,24 @@
+use crate::*;
+
+mod interval;
+mod root;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  match args.subcommand() {
+    ("interval", Some(m)) => interval::exec(ctx, m),
+    _ => root::exec(ctx, args),
+  }
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  let sub = SubCommand::with_name("edit")
+    .setting(AppSettings::ArgRequiredElseHelp)
+    .about("Edits record");
+  let sub = root::register(sub);
+  let sub = interval::register(sub);
+
+  app.subcommand(sub)
+}
@@ -0,0 +1,61 @@
+use crate::pick::*;
+use crate::select::*;
+use crate::{parse::*, *};
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let id = select_task(
+    ctx.db,
+    &value_or_pick(ctx, args, "ID", PickKind::Task)?,
+  )?
+  .id;
+  let estimate = if args.is_present("clear") {
+    None
+  } else {
+    Some(parse_duration(args.value_of("DURATION").unwrap())?)
+  };
+
+  let mut path = ctx.db.ancestors(id)?;
+  let node = path.last_mut().unwrap();
+  node.set_estimate(estimate);
+  ctx.db.save(node)?;
+
+  ctx.printer.node_cmd(&NodeCmdData {
+    cmd_text: if estimate.is_some() {
+      "Estimate updated."
+    } else {
+      "Estimate cleared."
+    },
+    node: NodeData {
+      title: NodeData::default_title(),
+      node: &path,
+    },
+  });
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("estimate")
+      .setting(AppSettings::AllowMissingPositional)
+      .about("Sets time estimate for a task")
+      .setting(AppSettings::ArgRequiredElseHelp)
+      .setting(AppSettings::AllowNegativeNumbers)
+      .arg(Arg::with_name("ID").help(TASK_SELECTOR_HELP))
+      .arg(
+        Arg::with_name("DURATION")
+          .help("Estimated time (e.g. 8h)")
+          .required_unless("clear"),
+      )
+      .arg(
+        Arg::with_name("clear")
+          .short("c")
+          .long("clear")
+          .help("Removes estimate")
+          .conflicts_with("DURATION"),
+      ),
+  )
+}
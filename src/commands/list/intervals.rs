@@ -1,3 +1,4 @@
+use crate::select::*;
 use crate::{core::Interval, *};
 use yatt_orm::statement::*;
 
@@ -5,12 +6,8 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
   ctx: &AppContext<T, P>,
   args: &ArgMatches,
 ) -> CliResult<()> {
-  let task_id: usize = if args.is_present("task") {
-    args.value_of("task").unwrap().parse().map_err(|_| {
-      CliError::Parse {
-        message: "Unable to parse task ID".into(),
-      }
-    })?
+  let task_id: usize = if let Some(task) = args.value_of("task") {
+    select_task(ctx.db, task)?.id
   } else {
     0
   };
@@ -44,7 +41,8 @@ pub fn register<'a>(app: App<'a, 'a>) -> App {
         Arg::with_name("task")
           .short("t")
           .long("task")
-          .help("Filtered task ID")
+          .help(TASK_SELECTOR_HELP)
+          .allow_hyphen_values(true)
           .takes_value(true)
           )
       // .arg(
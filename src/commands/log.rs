@@ -0,0 +1,108 @@
+use crate::core::{local_offset, Interval};
+use crate::parse::*;
+use crate::validate::*;
+use crate::*;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let (begin, end) = if let Some(period) = args.values_of("period") {
+    parse_period(
+      &period.collect::<Vec<_>>().join(" "),
+      &PeriodOpts::new(&ctx.conf, ctx.tz)?,
+    )?
+  } else if let (Some(from), Some(to)) =
+    (args.value_of("from"), args.value_of("to"))
+  {
+    (
+      parse_date_time(from, &ctx.tz)?,
+      parse_date_time(to, &ctx.tz)?,
+    )
+  } else {
+    return Err(CliError::Cmd {
+      message: "Interval bounds are not given, use --from and --to \
+        or --period"
+        .into(),
+    });
+  };
+
+  let mut interval = Interval {
+    id: 0,
+    node_id: None,
+    begin,
+    end: Some(end),
+    deleted: false,
+    closed: false,
+    pomodoro: false,
+    utc_offset: Some(local_offset(begin)),
+  };
+  check_bounds(&interval)?;
+  if !args.is_present("force") {
+    check_overlaps(ctx.db, &interval)?;
+  }
+
+  let path: Vec<&str> = args.values_of("TASK").unwrap().collect();
+  let path = path.join(" ");
+  let path: Vec<&str> = path.split("::").map(|t| t.trim()).collect();
+
+  let nodes = ctx.db.create_path(&path)?;
+  interval.node_id = Some(nodes.last().unwrap().id);
+  interval.id = ctx.db.save(&interval)?;
+
+  ctx.printer.interval_cmd(&IntervalCmdData {
+    cmd_text: "New interval created",
+    interval: IntervalData {
+      interval: &interval,
+      task: &nodes,
+      title: IntervalData::default_title(),
+    },
+  });
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("log")
+      .about("Adds finished interval for past work")
+      .setting(AppSettings::ArgRequiredElseHelp)
+      .arg(
+        Arg::with_name("TASK")
+          .help("Task name with nested tasks, delimited by \"::\"")
+          .required(true)
+          .multiple(true),
+      )
+      .arg(
+        Arg::with_name("from")
+          .short("f")
+          .long("from")
+          .help("Interval begin date and time")
+          .takes_value(true)
+          .allow_hyphen_values(true)
+          .requires("to"),
+      )
+      .arg(
+        Arg::with_name("to")
+          .long("to")
+          .help("Interval end date and time")
+          .takes_value(true)
+          .allow_hyphen_values(true)
+          .requires("from"),
+      )
+      .arg(
+        Arg::with_name("period")
+          .short("p")
+          .long("period")
+          .help("Interval period (instead of --from and --to)")
+          .takes_value(true)
+          .multiple(true)
+          .conflicts_with_all(&["from", "to"]),
+      )
+      .arg(
+        Arg::with_name("force")
+          .long("force")
+          .help("Allow overlapping with existing intervals"),
+      ),
+  )
+}
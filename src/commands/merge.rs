@@ -1,40 +1,34 @@
-use crate::{core::Interval, *};
+use crate::core::*;
+use crate::pick::*;
+use crate::select::*;
+use crate::*;
+use crossterm_input::input;
 use yatt_orm::statement::*;
 
 pub(crate) fn exec<T: DBRoot, P: Printer>(
   ctx: &AppContext<T, P>,
   args: &ArgMatches,
 ) -> CliResult<()> {
-  let from_id: usize =
-    args.value_of("FROM_ID").unwrap().parse().map_err(|_| {
-      CliError::Parse {
-        message: "Unable to parse FROM_ID".into(),
-      }
-    })?;
-  let to_id: usize =
-    args.value_of("TO_ID").unwrap().parse().map_err(|_| {
-      CliError::Parse {
-        message: "Unable to parse TO_ID".into(),
-      }
-    })?;
+  let from = select_task(
+    ctx.db,
+    &value_or_pick(ctx, args, "FROM_ID", PickKind::Task)?,
+  )?;
+  let to = select_task(ctx.db, args.value_of("TO_ID").unwrap())?;
 
-  let from_path = ctx.db.ancestors(from_id)?;
-  let to_path = ctx.db.ancestors(to_id)?;
-
-  for interval in ctx
-    .db
-    .get_by_filter::<Interval>(eq(
-      Interval::node_id_n(),
-      from_path.last().unwrap().id,
-    ))?
-    .iter_mut()
-  {
-    interval.node_id = Some(to_id);
-    ctx.db.save(interval)?;
+  let from_path = ctx.db.ancestors(from.id)?;
+  let to_path = ctx.db.ancestors(to.id)?;
+  if to_path.iter().any(|n| n.id == from.id) {
+    return Err(CliError::Cmd {
+      message: if from.id == to.id {
+        "Can't merge task into itself".into()
+      } else {
+        "Can't merge task into it's own subtask".into()
+      },
+    });
   }
 
   ctx.printer.node_cmd(&NodeCmdData {
-    cmd_text: "Successfully merged.",
+    cmd_text: "",
     node: NodeData {
       title: "From: ",
       node: &from_path,
@@ -47,24 +41,142 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
       node: &to_path,
     },
   });
+  let plan = ctx.db.merge_plan(&from, &to)?;
+  check_plan(&plan)?;
+  ctx.printer.plain("Changes:");
+  for step in &plan {
+    ctx.printer.plain(&describe_step(ctx.db, step)?);
+  }
+
+  if !args.is_present("yes") {
+    ctx.printer.prompt("Are you sure, you want to merge? [y/n]");
+    if input()
+      .read_char()
+      .map_err(|e| CliError::wrap(Box::new(e)))
+      .unwrap_or_default()
+      != 'y'
+    {
+      ctx.printer.plain("Cancelled...");
+      return Ok(());
+    }
+  }
+
+  ctx.db.merge_node(&from, &to)?;
+  ctx.printer.plain("Successfully merged.");
 
   Ok(())
 }
 
+/// Rejects plans, where a node is merged into a node, which is
+/// itself merged and deleted, e.g. `A::B` into `A`, when `B` has
+/// a subtask `B`.
+fn check_plan(plan: &[MergeStep]) -> CliResult<()> {
+  let merged: Vec<usize> = plan
+    .iter()
+    .filter_map(|s| match s {
+      MergeStep::Merge { from, .. } => Some(from.id),
+      _ => None,
+    })
+    .collect();
+  for step in plan {
+    let (node, to) = match step {
+      MergeStep::Merge { from, to, .. } => (from, to),
+      MergeStep::Move { node, to, .. } => (node, to),
+    };
+    if merged.contains(&to.id) {
+      return Err(CliError::Cmd {
+        message: format!(
+          "Can't merge, subtask \"{}\" ({}) would be merged \
+          into task \"{}\" ({}), which is deleted by the merge",
+          node.label, node.id, to.label, to.id
+        ),
+      });
+    }
+  }
+
+  Ok(())
+}
+
+/// Describes a change, which `DBRoot::merge_node` would make.
+fn describe_step<T: DBRoot>(
+  db: &T,
+  step: &MergeStep,
+) -> CliResult<String> {
+  let (from, to, depth) = match step {
+    MergeStep::Merge { from, to, depth } => (from, to, depth),
+    MergeStep::Move { node, to, depth } => {
+      return Ok(format!(
+        "{}move \"{}\" ({}){} under \"{}\" ({})",
+        "  ".repeat(depth + 1),
+        node.label,
+        node.id,
+        if node.deleted { " (deleted)" } else { "" },
+        to.label,
+        to.id
+      ))
+    }
+  };
+
+  let intervals =
+    db.get_by_filter::<Interval>(eq(Interval::node_id_n(), from.id))?;
+  let deleted = intervals.iter().filter(|i| i.deleted).count();
+  let to_tags = to.get_tags();
+  let new_tags: Vec<String> = from
+    .get_tags()
+    .into_iter()
+    .filter(|t| !t.is_empty() && !to_tags.contains(t))
+    .collect();
+
+  let mut line = format!(
+    "{}merge \"{}\" ({}){} into \"{}\" ({})",
+    "  ".repeat(depth + 1),
+    from.label,
+    from.id,
+    if from.deleted { " (deleted)" } else { "" },
+    to.label,
+    to.id
+  );
+  if !intervals.is_empty() {
+    line += &format!(", move {} interval(s)", intervals.len());
+    if deleted > 0 {
+      line += &format!(" ({} deleted)", deleted);
+    }
+  }
+  if !new_tags.is_empty() {
+    line += &format!(", add tags: {}", new_tags.join(", "));
+  }
+  if to.deleted && !from.deleted {
+    line += ", restore deleted target";
+  }
+
+  Ok(line)
+}
+
 pub fn register<'a>(app: App<'a, 'a>) -> App {
+  lazy_static! {
+    static ref FROM_HELP: String =
+      format!("Merge from task. {}", TASK_SELECTOR_HELP);
+    static ref TO_HELP: String =
+      format!("Merge to task. {}", TASK_SELECTOR_HELP);
+  }
   app.subcommand(
     SubCommand::with_name("merge")
-      .about("Moves all intervals from one task to another")
-      .setting(AppSettings::ArgRequiredElseHelp)
-      .arg(
-        Arg::with_name("FROM_ID")
-          .help("Merge from task id")
-          .required(true),
+      .setting(AppSettings::AllowMissingPositional)
+      .about(
+        "Merges one task into another with all subtasks\n\
+        Subtasks with the same label are merged, the rest are \
+        moved. Intervals are moved and tags are added to the \
+        target, merged tasks are deleted.",
       )
+      .setting(AppSettings::ArgRequiredElseHelp)
+      .setting(AppSettings::AllowNegativeNumbers)
+      .arg(Arg::with_name("FROM_ID").help(&FROM_HELP))
+      .arg(Arg::with_name("TO_ID").help(&TO_HELP).required(true))
       .arg(
-        Arg::with_name("TO_ID")
-          .help("Merge to task id")
-          .required(true),
+        Arg::with_name("yes")
+          .short("y")
+          .long("yes")
+          .help("Merge with no prompt"),
       ),
   )
 }
@@ -2,18 +2,30 @@ use crate::*;
 
 mod add;
 mod cancel;
+mod clone;
 mod delete;
+mod doctor;
+mod edit;
+mod estimate;
 mod list;
+mod log;
 mod merge;
 mod r#move;
+mod period;
+mod pick;
+mod pomodoro;
+mod rate;
 mod rename;
 mod reports;
 mod restart;
 mod root;
+mod split;
 mod start;
 mod state;
 mod stop;
+mod switch;
 mod tag;
+mod template;
 mod themes;
 mod truncate;
 mod untag;
@@ -24,19 +36,31 @@ pub fn exec<T: DBRoot, P: Printer>(
   match ctx.args.subcommand() {
     ("start", Some(m)) => start::exec(ctx, m),
     ("stop", Some(m)) => stop::exec(ctx, m),
+    ("switch", Some(m)) => switch::exec(ctx, m),
     ("restart", Some(m)) => restart::exec(ctx, m),
     ("state", Some(m)) => state::exec(ctx, m),
     ("report", Some(m)) => reports::exec(ctx, m),
     ("cancel", Some(m)) => cancel::exec(ctx, m),
     ("delete", Some(m)) => delete::exec(ctx, m),
+    ("edit", Some(m)) => edit::exec(ctx, m),
+    ("split", Some(m)) => split::exec(ctx, m),
+    ("log", Some(m)) => log::exec(ctx, m),
+    ("doctor", Some(m)) => doctor::exec(ctx, m),
+    ("pick", Some(m)) => pick::exec(ctx, m),
+    ("pomodoro", Some(m)) => pomodoro::exec(ctx, m),
     ("list", Some(m)) => list::exec(ctx, m),
     ("add", Some(m)) => add::exec(ctx, m),
     ("trunc", Some(m)) => truncate::exec(ctx, m),
     ("rename", Some(m)) => rename::exec(ctx, m),
     ("move", Some(m)) => r#move::exec(ctx, m),
     ("merge", Some(m)) => merge::exec(ctx, m),
+    ("clone", Some(m)) => clone::exec(ctx, m),
+    ("period", Some(m)) => period::exec(ctx, m),
+    ("template", Some(m)) => template::exec(ctx, m),
     ("tag", Some(m)) => tag::exec(ctx, m),
     ("untag", Some(m)) => untag::exec(ctx, m),
+    ("estimate", Some(m)) => estimate::exec(ctx, m),
+    ("rate", Some(m)) => rate::exec(ctx, m),
     ("themes", Some(m)) => themes::exec(ctx, m),
     _ => root::exec(ctx, &ctx.args),
   }
@@ -46,6 +70,7 @@ pub fn register<'a>(app: App<'a, 'a>) -> App {
   let app = root::register(app);
   let app = start::register(app);
   let app = stop::register(app);
+  let app = switch::register(app);
   let app = restart::register(app);
   let app = state::register(app);
   let app = cancel::register(app);
@@ -56,9 +81,37 @@ pub fn register<'a>(app: App<'a, 'a>) -> App {
   let app = rename::register(app);
   let app = r#move::register(app);
   let app = merge::register(app);
+  let app = clone::register(app);
+  let app = period::register(app);
+  let app = template::register(app);
   let app = tag::register(app);
   let app = untag::register(app);
+  let app = estimate::register(app);
+  let app = rate::register(app);
   let app = themes::register(app);
+  let app = edit::register(app);
+  let app = split::register(app);
+  let app = log::register(app);
+  let app = doctor::register(app);
+  let app = pick::register(app);
+  let app = pomodoro::register(app);
 
   delete::register(app)
 }
+
+pub(crate) fn at_arg<'a>() -> Arg<'a, 'a> {
+  Arg::with_name("at")
+    .long("at")
+    .help("Date and time to use instead of now")
+    .takes_value(true)
+    .allow_hyphen_values(true)
+    .conflicts_with("ago")
+}
+
+pub(crate) fn ago_arg<'a>() -> Arg<'a, 'a> {
+  Arg::with_name("ago")
+    .long("ago")
+    .help("Duration before now to use instead of now")
+    .takes_value(true)
+    .conflicts_with("at")
+}
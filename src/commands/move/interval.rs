@@ -0,0 +1,126 @@
+use crate::core::*;
+use crate::parse::*;
+use crate::pick::*;
+use crate::select::*;
+use crate::*;
+use yatt_orm::statement::*;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  // Single positional argument is the target task, intervals are
+  // selected by --task or interactively then.
+  let (selector, target) = match (
+    args.value_of("ID"),
+    args.value_of("TASK"),
+    args.value_of("task"),
+  ) {
+    (None, _, _) => {
+      return Err(CliError::Cmd {
+        message: "TASK is not given".into(),
+      })
+    }
+    (Some(_), Some(_), Some(_)) => {
+      return Err(CliError::Cmd {
+        message: "Interval ID can't be used along with --task".into(),
+      })
+    }
+    (Some(target), None, _) => (None, target),
+    (Some(id), Some(target), None) => (Some(id), target),
+  };
+  let target = select_task(ctx.db, target)?;
+
+  let intervals = if let Some(task) = args.value_of("task") {
+    let source = select_task(ctx.db, task)?;
+    let period: Vec<&str> =
+      args.values_of("period").unwrap().collect();
+    let (start, end) = parse_period(
+      &period.join(" "),
+      &PeriodOpts::new(&ctx.conf, ctx.tz)?,
+    )?;
+    ctx.db.get_by_statement(
+      filter(and(
+        and(
+          eq(Interval::node_id_n(), source.id),
+          ne(Interval::deleted_n(), 1),
+        ),
+        and(
+          not(lt(Interval::begin_n(), start)),
+          lt(Interval::begin_n(), end),
+        ),
+      ))
+      .sort(Interval::begin_n(), SortDir::Ascend),
+    )?
+  } else if let Some(selector) = selector {
+    select_intervals(ctx.db, selector)?
+  } else {
+    select_intervals(
+      ctx.db,
+      &pick_value(ctx, "ID", PickKind::Interval)?,
+    )?
+  };
+
+  let mut intervals: Vec<Interval> = intervals
+    .into_iter()
+    .filter(|i| i.node_id != Some(target.id))
+    .collect();
+  if intervals.is_empty() {
+    return Err(CliError::Cmd {
+      message: "There is no intervals to move".into(),
+    });
+  }
+
+  let task = ctx.db.ancestors(target.id)?;
+  let mut cmd_text = "Successfully moved.";
+  for interval in intervals.iter_mut() {
+    interval.node_id = Some(target.id);
+    ctx.db.save(interval)?;
+    ctx.printer.interval_cmd(&IntervalCmdData {
+      cmd_text,
+      interval: IntervalData {
+        interval,
+        task: &task,
+        title: &format!("Interval {}:", interval.id),
+      },
+    });
+    cmd_text = "";
+  }
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("interval")
+      .setting(AppSettings::ArgRequiredElseHelp)
+      .setting(AppSettings::AllowNegativeNumbers)
+      .about("Moves intervals to another task")
+      .arg(Arg::with_name("ID").help(
+        "[ID], -[offset] from NOW (starting with -1), or range \
+            of them, delimited by \"..\" (e.g. 10..12, or \
+            -- -3..-1 for offsets)",
+      ))
+      .arg(Arg::with_name("TASK").help(TASK_SELECTOR_HELP))
+      .arg(
+        Arg::with_name("task")
+          .short("t")
+          .long("task")
+          .help(
+            "Move intervals of this task instead of given by ID, \
+            target task should be the only positional argument",
+          )
+          .allow_hyphen_values(true)
+          .takes_value(true)
+          .requires("period"),
+      )
+      .arg(
+        Arg::with_name("period")
+          .short("p")
+          .long("period")
+          .help("Period, intervals of the task started within")
+          .takes_value(true)
+          .multiple(true),
+      ),
+  )
+}
@@ -1,5 +1,6 @@
 use crate::*;
 
+mod interval;
 mod root;
 mod task;
 
@@ -9,6 +10,7 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
 ) -> CliResult<()> {
   match args.subcommand() {
     ("task", Some(m)) => task::exec(ctx, m),
+    ("interval", Some(m)) => interval::exec(ctx, m),
     _ => root::exec(ctx, args),
   }
 }
@@ -20,6 +22,7 @@ pub fn register<'a>(app: App<'a, 'a>) -> App {
     .about("Moves task or interval");
   let sub = root::register(sub);
   let sub = task::register(sub);
+  let sub = interval::register(sub);
 
   app.subcommand(sub)
 }
@@ -1,37 +1,35 @@
-use crate::{core::Node, *};
+use crate::pick::*;
+use crate::select::*;
+use crate::*;
 
 pub(crate) fn exec<T: DBRoot, P: Printer>(
   ctx: &AppContext<T, P>,
   args: &ArgMatches,
 ) -> CliResult<()> {
-  let id: usize =
-    args.value_of("ID").unwrap().parse().map_err(|_| {
-      CliError::Parse {
-        message: "Unable to parse task ID".into(),
-      }
-    })?;
-  let parent_id: usize =
-    args.value_of("PARENT_ID").unwrap().parse().map_err(|_| {
-      CliError::Parse {
-        message: "Unable to parse PARENT_ID".into(),
-      }
-    })?;
+  let mut node = select_task(
+    ctx.db,
+    &value_or_pick(ctx, args, "ID", PickKind::Task)?,
+  )?;
+  let parent = args.value_of("PARENT_ID").unwrap();
+  let parent_id = if parent.trim() == "0" {
+    None
+  } else {
+    Some(select_task(ctx.db, parent)?.id)
+  };
 
-  if id == parent_id {
+  if Some(node.id) == parent_id {
     return Err(CliError::Cmd {
       message: "It is impossible to move the task to itself".into(),
     });
   }
 
-  let mut node: Node = ctx.db.get_by_id(id)?;
-
-  let mut path = if parent_id > 0 {
+  let mut path = if let Some(parent_id) = parent_id {
     ctx.db.ancestors(parent_id)?
   } else {
     Vec::new()
   };
 
-  node.parent_id = Some(parent_id);
+  node.parent_id = parent_id;
   ctx.db.save(&node)?;
   path.push(node);
 
@@ -49,12 +47,14 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
 pub fn register<'a>(app: App<'a, 'a>) -> App {
   app.subcommand(
     SubCommand::with_name("task")
+      .setting(AppSettings::AllowMissingPositional)
       .about("Moves task to new parent")
       .setting(AppSettings::ArgRequiredElseHelp)
-      .arg(Arg::with_name("ID").help("Task id").required(true))
+      .setting(AppSettings::AllowNegativeNumbers)
+      .arg(Arg::with_name("ID").help(TASK_SELECTOR_HELP))
       .arg(
         Arg::with_name("PARENT_ID")
-          .help("New parent id")
+          .help("New parent task selector, or 0 to make task a root")
           .required(true),
       ),
   )
@@ -0,0 +1,52 @@
+use crate::format::*;
+use crate::parse::*;
+use crate::*;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let expr: Vec<&str> = args.values_of("EXPR").unwrap().collect();
+  let (start, end) = parse_period(
+    &expr.join(" "),
+    &PeriodOpts::new(&ctx.conf, ctx.tz)?,
+  )?;
+
+  let opts = DateTimeOpts {
+    always_long: true,
+    no_string_now: true,
+  };
+  ctx.printer.plain(&format!(
+    "Begin:    {}",
+    format_datetime_opts(&start.with_timezone(&ctx.tz), &opts)
+  ));
+  ctx.printer.plain(&format!(
+    "End:      {}",
+    format_datetime_opts(&end.with_timezone(&ctx.tz), &opts)
+  ));
+  ctx
+    .printer
+    .plain(&format!("Duration: {}", format_duration(&(end - start))));
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("period")
+      .about(
+        "Prints bounds of the given period\n\
+        Accepts everything --period of reports does, e.g. \"pw\", \
+        \"this-month\", \"q\", \"2026-Q3\", \"2026-W41\", \"march\" \
+        or \"2026-01-01::2026-02-01\".",
+      )
+      .setting(AppSettings::ArgRequiredElseHelp)
+      .setting(AppSettings::AllowLeadingHyphen)
+      .arg(
+        Arg::with_name("EXPR")
+          .help("Period expression")
+          .required(true)
+          .multiple(true),
+      ),
+  )
+}
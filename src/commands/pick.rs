@@ -0,0 +1,38 @@
+use std::io::stdin;
+
+use crate::pick::*;
+use crate::*;
+use crossterm::tty::IsTty;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  if !stdin().is_tty() {
+    return Err(CliError::Cmd {
+      message: "Picker requires an interactive terminal".into(),
+    });
+  }
+
+  let kind: PickKind = args.value_of("KIND").unwrap().parse()?;
+  if let Some(key) = pick(ctx, kind)? {
+    // Printed with no styles to be used in scripts.
+    println!("{}", key);
+  }
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("pick")
+      .about("Interactively selects an entity and prints it's id")
+      .setting(AppSettings::ArgRequiredElseHelp)
+      .arg(
+        Arg::with_name("KIND")
+          .help("Kind of entity to pick")
+          .possible_values(PickKind::variants())
+          .required(true),
+      ),
+  )
+}
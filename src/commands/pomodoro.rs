@@ -0,0 +1,240 @@
+use std::io::{stdin, stdout, Write};
+use std::time;
+
+use chrono::Duration;
+use crossterm::cursor::MoveToColumn;
+use crossterm::event::{
+  poll, read, Event, KeyCode, KeyEvent, KeyModifiers,
+};
+use crossterm::style::Print;
+use crossterm::terminal::{
+  disable_raw_mode, enable_raw_mode, Clear, ClearType,
+};
+use crossterm::tty::IsTty;
+use crossterm::{execute, queue};
+
+use crate::core::Interval;
+use crate::parse::*;
+use crate::validate::*;
+use crate::*;
+
+enum Phase {
+  Completed,
+  Skipped,
+  Stopped(DateTime<Utc>),
+}
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  if !stdin().is_tty() {
+    return Err(CliError::Cmd {
+      message: "Pomodoro requires an interactive terminal".into(),
+    });
+  }
+  if let Some((node, interval)) = ctx.db.cur_running()? {
+    let task = ctx.db.ancestors(node.id)?;
+    return Err(CliError::Task {
+      source: TaskError::CmdTaskInterval {
+        message: "Interval already running.".to_string(),
+        interval,
+        task,
+      },
+    });
+  }
+
+  let conf = &ctx.conf.pomodoro;
+  let work =
+    parse_duration(args.value_of("work").unwrap_or(&conf.work))?;
+  let short_break = parse_duration(
+    args.value_of("break").unwrap_or(&conf.short_break),
+  )?;
+  let long_break = parse_duration(
+    args.value_of("long-break").unwrap_or(&conf.long_break),
+  )?;
+  let count: Option<usize> =
+    if let Some(count) = args.value_of("count") {
+      Some(count.parse().map_err(|_| CliError::Parse {
+        message: "Unable to parse pomodoros count".into(),
+      })?)
+    } else {
+      None
+    };
+
+  let path: Vec<&str> = args.values_of("TASK").unwrap().collect();
+  let path = path.join(" ");
+  let path: Vec<&str> = path.split("::").map(|t| t.trim()).collect();
+  let nodes = ctx.db.create_path(&path)?;
+  let title = format_task_name(&nodes);
+
+  ctx.printer.node_cmd(&NodeCmdData {
+    cmd_text: "Starting pomodoro... Press [q] to quit.",
+    node: NodeData {
+      title: NodeData::default_title(),
+      node: &nodes,
+    },
+  });
+  ctx.printer.plain("");
+
+  let mut done = 0;
+  enable_raw_mode().map_err(|e| CliError::wrap(Box::new(e)))?;
+  let res = loop {
+    let mut interval = Interval {
+      node_id: Some(nodes.last().unwrap().id),
+      ..Interval::default()
+    };
+    match countdown(&format!("Work on {}", title), work, false) {
+      Ok(Phase::Stopped(at)) => {
+        if at > interval.begin {
+          interval.end = Some(at);
+          if let Err(e) = save(ctx, &interval) {
+            break Err(e);
+          }
+        }
+        break Ok(());
+      }
+      Ok(_) => {
+        interval.end = Some(interval.begin + work);
+        interval.pomodoro = true;
+        if let Err(e) = save(ctx, &interval) {
+          break Err(e);
+        }
+        done += 1;
+      }
+      Err(e) => break Err(CliError::wrap(Box::new(e))),
+    }
+    if Some(done) == count {
+      break Ok(());
+    }
+
+    let pause =
+      if done % ctx.conf.pomodoro.long_break_every.max(1) == 0 {
+        long_break
+      } else {
+        short_break
+      };
+    match countdown("Break", pause, true) {
+      Ok(Phase::Stopped(_)) => break Ok(()),
+      Ok(_) => {}
+      Err(e) => break Err(CliError::wrap(Box::new(e))),
+    }
+  };
+  disable_raw_mode().map_err(|e| CliError::wrap(Box::new(e)))?;
+  println!();
+
+  ctx.printer.plain(&format!("Completed pomodoros: {}", done));
+
+  res
+}
+
+fn save<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  interval: &Interval,
+) -> CliResult<()> {
+  check_overlaps(ctx.db, interval)?;
+  ctx.db.save(interval)?;
+  Ok(())
+}
+
+/// Draws countdown in the current line until given time passes,
+/// or user stops (or skips, if allowed) it.
+fn countdown(
+  title: &str,
+  length: Duration,
+  skippable: bool,
+) -> crossterm::Result<Phase> {
+  let mut out = stdout();
+  let end = Utc::now() + length;
+  let keys = if skippable {
+    "[s]kip, [q]uit"
+  } else {
+    "[q]uit"
+  };
+
+  loop {
+    let left = end - Utc::now();
+    if left <= Duration::zero() {
+      execute!(out, Print('\u{7}'))?;
+      return Ok(Phase::Completed);
+    }
+
+    let secs = left.num_seconds() + 1;
+    queue!(
+      out,
+      MoveToColumn(0),
+      Clear(ClearType::CurrentLine),
+      Print(format!(
+        "{}: {:02}:{:02} {}",
+        title,
+        secs / 60,
+        secs % 60,
+        keys
+      )),
+    )?;
+    out.flush()?;
+
+    if !poll(time::Duration::from_millis(200))? {
+      continue;
+    }
+    if let Event::Key(KeyEvent { code, modifiers }) = read()? {
+      match code {
+        KeyCode::Char('q') | KeyCode::Esc => {
+          return Ok(Phase::Stopped(Utc::now()))
+        }
+        KeyCode::Char('c')
+          if modifiers.contains(KeyModifiers::CONTROL) =>
+        {
+          return Ok(Phase::Stopped(Utc::now()))
+        }
+        KeyCode::Char('s') if skippable => return Ok(Phase::Skipped),
+        _ => {}
+      }
+    }
+  }
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("pomodoro")
+      .about(
+        "Runs work and break cycles for a task\n\
+        Each completed work block is saved as an interval.",
+      )
+      .setting(AppSettings::ArgRequiredElseHelp)
+      .arg(
+        Arg::with_name("TASK")
+          .help("Task name with nested tasks, delimited by \"::\"")
+          .required(true)
+          .multiple(true),
+      )
+      .arg(
+        Arg::with_name("work")
+          .short("w")
+          .long("work")
+          .help("Work block length (default from config, or 25m)")
+          .takes_value(true),
+      )
+      .arg(
+        Arg::with_name("break")
+          .short("b")
+          .long("break")
+          .help("Short break length (default from config, or 5m)")
+          .takes_value(true),
+      )
+      .arg(
+        Arg::with_name("long-break")
+          .short("l")
+          .long("long-break")
+          .help("Long break length (default from config, or 15m)")
+          .takes_value(true),
+      )
+      .arg(
+        Arg::with_name("count")
+          .short("n")
+          .long("count")
+          .help("Number of pomodoros to run")
+          .takes_value(true),
+      ),
+  )
+}
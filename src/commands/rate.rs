@@ -0,0 +1,72 @@
+use crate::pick::*;
+use crate::select::*;
+use crate::{parse::*, *};
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let id = select_task(
+    ctx.db,
+    &value_or_pick(ctx, args, "ID", PickKind::Task)?,
+  )?
+  .id;
+  let rate = if args.is_present("clear") {
+    None
+  } else {
+    let rate: Vec<&str> = args.values_of("RATE").unwrap().collect();
+    let mut rate = parse_rate(&rate.join(" "))?;
+    if rate.currency.is_empty() {
+      rate.currency = ctx.conf.currency.to_uppercase();
+    }
+    Some(rate)
+  };
+
+  let mut path = ctx.db.ancestors(id)?;
+  let node = path.last_mut().unwrap();
+  let cmd_text = if rate.is_some() {
+    "Rate updated."
+  } else {
+    "Rate cleared."
+  };
+  node.set_rate(rate);
+  ctx.db.save(node)?;
+
+  ctx.printer.node_cmd(&NodeCmdData {
+    cmd_text,
+    node: NodeData {
+      title: NodeData::default_title(),
+      node: &path,
+    },
+  });
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("rate")
+      .setting(AppSettings::AllowMissingPositional)
+      .about(
+        "Sets hourly rate for a task\n\
+        Rate is inherited by task children. Rates for tags \n\
+        could be set in the \"tag_rates\" config section.",
+      )
+      .setting(AppSettings::ArgRequiredElseHelp)
+      .setting(AppSettings::AllowNegativeNumbers)
+      .arg(Arg::with_name("ID").help(TASK_SELECTOR_HELP))
+      .arg(
+        Arg::with_name("RATE")
+          .help("Hourly rate with optional currency (e.g. 50.5 USD)")
+          .multiple(true)
+          .required_unless("clear"),
+      )
+      .arg(
+        Arg::with_name("clear")
+          .short("c")
+          .long("clear")
+          .help("Removes rate")
+          .conflicts_with("RATE"),
+      ),
+  )
+}
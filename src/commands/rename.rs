@@ -1,15 +1,16 @@
+use crate::pick::*;
+use crate::select::*;
 use crate::*;
 
 pub(crate) fn exec<T: DBRoot, P: Printer>(
   ctx: &AppContext<T, P>,
   args: &ArgMatches,
 ) -> CliResult<()> {
-  let id: usize =
-    args.value_of("ID").unwrap().parse().map_err(|_| {
-      CliError::Parse {
-        message: "Unable to parse task ID".into(),
-      }
-    })?;
+  let id = select_task(
+    ctx.db,
+    &value_or_pick(ctx, args, "ID", PickKind::Task)?,
+  )?
+  .id;
   let label: Vec<&str> = args.values_of("NAME").unwrap().collect();
   let label = label.join(" ");
 
@@ -31,9 +32,11 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
 pub fn register<'a>(app: App<'a, 'a>) -> App {
   app.subcommand(
     SubCommand::with_name("rename")
+      .setting(AppSettings::AllowMissingPositional)
       .about("Renames a task")
       .setting(AppSettings::ArgRequiredElseHelp)
-      .arg(Arg::with_name("ID").help("Task id").required(true))
+      .setting(AppSettings::AllowNegativeNumbers)
+      .arg(Arg::with_name("ID").help(TASK_SELECTOR_HELP))
       .arg(
         Arg::with_name("NAME")
           .help("New task name")
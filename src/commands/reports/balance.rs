@@ -0,0 +1,105 @@
+use chrono::Duration;
+
+use super::{
+  format_arg, get_intervals, get_period, period_arg, print_report,
+  tags_arg,
+};
+use crate::core::*;
+use crate::parse::PeriodOpts;
+use crate::report::*;
+use crate::schedule::Schedule;
+use crate::*;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let (start, end) = get_period(ctx, args)?;
+  let intervals = get_intervals(ctx, args, &start, &end)?;
+  let schedule = Schedule::new(&ctx.conf.schedule, &ctx.root)?;
+
+  let mut r = Report::new();
+  r.push("Work time balance.");
+  r.push((start.with_timezone(&ctx.tz), end.with_timezone(&ctx.tz)));
+  r.push(vec![
+    "Day".to_string(),
+    "Tracked".to_string(),
+    "Expected".to_string(),
+    "Difference".to_string(),
+    "Balance".to_string(),
+  ]);
+
+  let mut total_tracked = Duration::zero();
+  let mut total_expected = Duration::zero();
+  let opts = PeriodOpts::new(&ctx.conf, ctx.tz)?;
+  let mut day = opts.day_of(start.with_timezone(&opts.tz));
+  while opts.day_begin(day) < end {
+    let day_start = opts.day_begin(day).with_timezone(&Utc);
+    let day_end = opts.day_begin(day.succ()).with_timezone(&Utc);
+    let date = day.naive_local();
+    day = day.succ();
+
+    let tracked = spent_within(&intervals, &day_start, &day_end);
+    let expected = schedule.expected(&date);
+    let holiday = schedule.is_holiday(&date);
+    if tracked.is_zero() && expected.is_zero() && !holiday {
+      continue;
+    }
+    total_tracked += tracked;
+    total_expected += expected;
+
+    let mut label = date.format("%Y-%m-%d %a").to_string();
+    if holiday {
+      label += " (holiday)";
+    }
+    r.push(vec![
+      Cell::String(label),
+      Cell::Duration(tracked),
+      Cell::Duration(expected),
+      Cell::SignedDuration(tracked - expected),
+      Cell::SignedDuration(total_tracked - total_expected),
+    ]);
+  }
+
+  r.push(Row::Total(vec![
+    Cell::Duration(total_tracked),
+    Cell::Duration(total_expected),
+    Cell::SignedDuration(total_tracked - total_expected),
+    Cell::Span,
+  ]));
+
+  print_report(ctx, args, &r);
+
+  Ok(())
+}
+
+/// Returns time of intervals within given bounds.
+fn spent_within(
+  intervals: &[Interval],
+  start: &DateTime<Utc>,
+  end: &DateTime<Utc>,
+) -> Duration {
+  intervals.iter().fold(Duration::zero(), |acc, v| {
+    let b = v.begin.max(*start);
+    let e = v.end.unwrap().min(*end);
+    if e > b {
+      acc + (e - b)
+    } else {
+      acc
+    }
+  })
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("balance")
+      .about(
+        "Tracked time compared with work schedule for period \
+        (default - current day).\n\
+        Shows daily overtime or undertime and running balance.",
+      )
+      .arg(period_arg())
+      .arg(tags_arg())
+      .arg(format_arg()),
+  )
+}
@@ -0,0 +1,142 @@
+use chrono::Duration;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use super::{
+  find_intervals, format_arg, get_period, period_arg, print_report,
+  split_by_days, tags_arg, tags_filter, truncate_intervals,
+};
+use crate::core::*;
+use crate::parse::PeriodOpts;
+use crate::report::*;
+use crate::*;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let (start, end) = get_period(ctx, args)?;
+  let stored = find_intervals(ctx, &tags_filter(args), &start, &end)?;
+  let mut intervals = stored.clone();
+  truncate_intervals(&mut intervals, &start, &end);
+  let opts = PeriodOpts::new(&ctx.conf, ctx.tz)?;
+
+  let mut paths: HashMap<usize, Vec<Node>> = HashMap::new();
+  for interval in intervals.iter() {
+    let id = interval.node_id.unwrap();
+    if let Entry::Vacant(e) = paths.entry(id) {
+      e.insert(ctx.db.ancestors(id)?);
+    }
+  }
+  let parts: Vec<(Date<AppTz>, Interval)> =
+    split_by_days(&intervals, &opts)
+      .into_iter()
+      .filter(|(_, i)| {
+        !paths[&i.node_id.unwrap()].iter().any(|n| n.deleted)
+      })
+      .collect();
+
+  let mut r = Report::new();
+  r.set_nested_column("Task");
+  r.push("Daily time.");
+  r.push((start.with_timezone(&ctx.tz), end.with_timezone(&ctx.tz)));
+  r.push(vec![
+    "Day".to_string(),
+    "Time".to_string(),
+    "First start".to_string(),
+    "Last stop".to_string(),
+  ]);
+
+  // First start and last stop are taken from the stored intervals,
+  // so ones crossing the day start show their actual bounds.
+  let stored: HashMap<usize, &Interval> =
+    stored.iter().map(|i| (i.id, i)).collect();
+  let mut total = Duration::zero();
+  for day_parts in parts.chunk_by(|a, b| a.0 == b.0) {
+    let day = day_parts[0].0;
+    let first = day_parts
+      .iter()
+      .map(|(_, i)| stored[&i.id].begin)
+      .min()
+      .unwrap();
+    let last = day_parts
+      .iter()
+      .map(|(_, i)| stored[&i.id].end.unwrap_or_else(Utc::now))
+      .max()
+      .unwrap();
+
+    // Tasks in order of the first interval within the day.
+    let mut tasks: Vec<(usize, Duration)> = Vec::new();
+    for (_, interval) in day_parts {
+      let id = interval.node_id.unwrap();
+      let time = interval.end.unwrap() - interval.begin;
+      match tasks.iter_mut().find(|(t, _)| *t == id) {
+        Some((_, d)) => *d += time,
+        None => tasks.push((id, time)),
+      }
+    }
+    let day_total =
+      tasks.iter().fold(Duration::zero(), |acc, (_, d)| acc + *d);
+    total += day_total;
+
+    r.push(vec![
+      Cell::String(day.format("%Y-%m-%d %a").to_string()),
+      Cell::Duration(day_total),
+      Cell::String(format_time(&first, day, &opts)),
+      Cell::String(format_time(&last, day, &opts)),
+    ]);
+    for (id, time) in tasks {
+      r.push(Row::Nested(vec![
+        Cell::Nested(
+          Box::new(Cell::String(format_task_name(&paths[&id]))),
+          1,
+        ),
+        Cell::Duration(time),
+        Cell::Span,
+        Cell::Span,
+      ]));
+    }
+  }
+
+  if !total.is_zero() {
+    r.push(Row::Total(vec![
+      Cell::Duration(total),
+      Cell::Span,
+      Cell::Span,
+    ]));
+  }
+
+  print_report(ctx, args, &r);
+
+  Ok(())
+}
+
+/// Formats time of the moment, marking it with a number of days
+/// (e.g. "02:00 +1"), if it belongs to another day.
+fn format_time(
+  dt: &DateTime<Utc>,
+  day: Date<AppTz>,
+  opts: &PeriodOpts,
+) -> String {
+  let dt = dt.with_timezone(&opts.tz);
+  let time = dt.format("%H:%M").to_string();
+  match (opts.day_of(dt) - day).num_days() {
+    0 => time,
+    days => format!("{} {:+}", time, days),
+  }
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("daily")
+      .about(
+        "Time per day for period (default - current day).\n\
+        Lists tasks worked on each day along with the first start \
+        and the last stop. Intervals crossing the day start are \
+        split between days.",
+      )
+      .arg(period_arg())
+      .arg(tags_arg())
+      .arg(format_arg()),
+  )
+}
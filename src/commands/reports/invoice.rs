@@ -0,0 +1,187 @@
+use chrono::Duration;
+use std::collections::HashMap;
+
+use super::{
+  format_arg, get_intervals, get_period, period_arg, print_report,
+  tags_arg,
+};
+use crate::core::*;
+use crate::parse::parse_rate;
+use crate::report::*;
+use crate::*;
+
+struct Line {
+  path: Vec<Node>,
+  spent: Duration,
+  rate: Rate,
+}
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let (start, end) = get_period(ctx, args)?;
+  let intervals = get_intervals(ctx, args, &start, &end)?;
+  let tag_rates = get_tag_rates(&ctx.conf)?;
+
+  let mut spent: HashMap<usize, Duration> = HashMap::new();
+  for interval in intervals.iter() {
+    let v = spent
+      .entry(interval.node_id.unwrap())
+      .or_insert_with(Duration::zero);
+    *v += interval.end.unwrap() - interval.begin;
+  }
+
+  let mut lines = Vec::new();
+  for (id, spent) in spent {
+    if spent.num_seconds() == 0 {
+      continue;
+    }
+    let path = ctx.db.ancestors(id)?;
+    if path.iter().any(|v| v.deleted) {
+      continue;
+    }
+    if let Some(mut rate) = effective_rate(&path, &tag_rates) {
+      if rate.currency.is_empty() {
+        rate.currency = ctx.conf.currency.to_uppercase();
+      }
+      lines.push(Line {
+        path,
+        spent: Duration::seconds(spent.num_seconds()),
+        rate,
+      });
+    }
+  }
+  lines.sort_by(|a, b| {
+    a.path
+      .iter()
+      .map(|n| &n.label)
+      .cmp(b.path.iter().map(|n| &n.label))
+  });
+
+  let mut r = Report::new();
+  r.push("Invoice.");
+  r.push((start.with_timezone(&ctx.tz), end.with_timezone(&ctx.tz)));
+  if !lines.is_empty() {
+    r.push(vec![
+      "Task".to_string(),
+      "Time".to_string(),
+      "Rate".to_string(),
+      "Amount".to_string(),
+    ]);
+  }
+
+  let mut totals: Vec<(String, Duration, usize)> = Vec::new();
+  for client in lines.chunk_by(|a, b| a.path[0].id == b.path[0].id) {
+    r.push(vec![Cell::String(client[0].path[0].label.to_owned())]);
+
+    let mut sub_totals: Vec<(String, Duration, usize)> = Vec::new();
+    for line in client {
+      let label = if line.path.len() > 1 {
+        format_task_name(&line.path[1..])
+      } else {
+        line.path[0].label.to_owned()
+      };
+      let amount = line.rate.cost(&line.spent);
+      r.push(Row::Nested(vec![
+        Cell::Nested(Box::new(Cell::String(label)), 1),
+        Cell::Duration(line.spent),
+        Cell::String(format_rate(&line.rate)),
+        Cell::Money(amount, line.rate.currency.to_owned()),
+      ]));
+      add_to_totals(
+        &mut sub_totals,
+        &line.rate.currency,
+        line.spent,
+        amount,
+      );
+      add_to_totals(
+        &mut totals,
+        &line.rate.currency,
+        line.spent,
+        amount,
+      );
+    }
+
+    if client.len() > 1 {
+      for (currency, spent, amount) in sub_totals {
+        r.push(Row::SubTotal(vec![
+          Cell::Duration(spent),
+          Cell::Span,
+          Cell::Money(amount, currency),
+        ]));
+      }
+    }
+  }
+
+  for (currency, spent, amount) in totals {
+    r.push(Row::Total(vec![
+      Cell::Duration(spent),
+      Cell::Span,
+      Cell::Money(amount, currency),
+    ]));
+  }
+
+  print_report(ctx, args, &r);
+
+  Ok(())
+}
+
+fn add_to_totals(
+  totals: &mut Vec<(String, Duration, usize)>,
+  currency: &str,
+  spent: Duration,
+  amount: usize,
+) {
+  if let Some(v) = totals.iter_mut().find(|v| v.0 == currency) {
+    v.1 += spent;
+    v.2 += amount;
+  } else {
+    totals.push((currency.to_string(), spent, amount));
+  }
+}
+
+fn get_tag_rates(conf: &AppConfig) -> CliResult<Vec<(String, Rate)>> {
+  let mut res = Vec::new();
+  for (tag, rate) in conf.tag_rates.iter() {
+    res.push((tag.to_lowercase(), parse_rate(rate)?));
+  }
+  res.sort_by(|a, b| a.0.cmp(&b.0));
+
+  Ok(res)
+}
+
+/// Returns rate of the nearest node of the path, which has
+/// own rate or a tag with rate.
+fn effective_rate(
+  path: &[Node],
+  tag_rates: &[(String, Rate)],
+) -> Option<Rate> {
+  for node in path.iter().rev() {
+    if let Some(rate) = node.get_rate() {
+      return Some(rate);
+    }
+    let tags = node.get_tags();
+    for (tag, rate) in tag_rates {
+      if tags.contains(tag) {
+        return Some(rate.clone());
+      }
+    }
+  }
+
+  None
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("invoice")
+      .about(
+        "Billable time and amounts for period \
+        (default - currernt day).\n\
+        Subtotals are grouped by client (top level task).",
+      )
+      .arg(period_arg())
+      .arg(tags_arg())
+      .arg(format_arg()),
+  )
+}
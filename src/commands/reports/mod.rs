@@ -1,6 +1,16 @@
+use crate::core::*;
+use crate::parse::*;
+use crate::report::Report;
 use crate::*;
+use yatt_orm::statement::*;
+use yatt_orm::FieldVal;
 
+mod balance;
+mod daily;
+mod invoice;
 mod root;
+mod tags;
+mod timesheet;
 mod total;
 
 pub(crate) fn exec<T: DBRoot, P: Printer>(
@@ -9,6 +19,11 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
 ) -> CliResult<()> {
   match args.subcommand() {
     ("total", Some(m)) => total::exec(ctx, m),
+    ("invoice", Some(m)) => invoice::exec(ctx, m),
+    ("balance", Some(m)) => balance::exec(ctx, m),
+    ("daily", Some(m)) => daily::exec(ctx, m),
+    ("timesheet", Some(m)) => timesheet::exec(ctx, m),
+    ("tags", Some(m)) => tags::exec(ctx, m),
     _ => root::exec(ctx, args),
   }
 }
@@ -19,6 +34,206 @@ pub fn register<'a>(app: App<'a, 'a>) -> App {
     .about("Shows selected report");
   let sub = root::register(sub);
   let sub = total::register(sub);
+  let sub = invoice::register(sub);
+  let sub = balance::register(sub);
+  let sub = daily::register(sub);
+  let sub = timesheet::register(sub);
+  let sub = tags::register(sub);
 
   app.subcommand(sub)
 }
+
+/// Returns report period from "period" argument,
+/// current day by default.
+pub(crate) fn get_period<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<(DateTime<Utc>, DateTime<Utc>)> {
+  let opts = PeriodOpts::new(&ctx.conf, ctx.tz)?;
+  if let Some(v) = args.values_of("period") {
+    parse_period(&v.collect::<Vec<_>>().join(" "), &opts)
+  } else {
+    Ok((opts.day_begin(opts.today()).with_timezone(&Utc), Utc::now()))
+  }
+}
+
+/// Returns not deleted intervals, which intersects with given period
+/// and matches "tags" argument, sorted by begin. Intervals are
+/// truncated to the period bounds, running interval is closed
+/// at the period end.
+pub(crate) fn get_intervals<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+  start: &DateTime<Utc>,
+  end: &DateTime<Utc>,
+) -> CliResult<Vec<Interval>> {
+  let mut intervals =
+    find_intervals(ctx, &tags_filter(args), start, end)?;
+  truncate_intervals(&mut intervals, start, end);
+
+  Ok(intervals)
+}
+
+/// Returns tags from "tags" argument, ones to exclude start
+/// with "^".
+pub(crate) fn tags_filter(args: &ArgMatches) -> Vec<String> {
+  if let Some(tags) = args.value_of("tags") {
+    tags
+      .split(',')
+      .filter(|v| !v.is_empty())
+      .map(|v| v.to_lowercase())
+      .collect()
+  } else {
+    Vec::new()
+  }
+}
+
+/// Checks, whether tags of any of the nodes match the filter the
+/// same way, as they are matched by [find_intervals].
+pub(crate) fn matches_tags(nodes: &[Node], tags: &[String]) -> bool {
+  tags.iter().all(|tag| {
+    let neg = tag.starts_with('^');
+    let tag = tag.trim_start_matches('^');
+    nodes.iter().any(|n| n.tags.to_lowercase().contains(tag)) != neg
+  })
+}
+
+/// Same as [get_intervals], but intervals are filtered by given
+/// tags and returned as stored.
+pub(crate) fn find_intervals<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  tags: &[String],
+  start: &DateTime<Utc>,
+  end: &DateTime<Utc>,
+) -> CliResult<Vec<Interval>> {
+  let mut tag_filters = None;
+  for tag in tags {
+    let neg = tag.starts_with('^');
+    let tag = tag.trim_start_matches('^');
+    let fltr = if neg {
+      not(includes(Node::tags_n(), tag))
+    } else {
+      includes(Node::tags_n(), tag)
+    };
+
+    tag_filters = if let Some(prev_fltr) = tag_filters {
+      Some(and(prev_fltr, fltr))
+    } else {
+      Some(fltr)
+    }
+  }
+  let mut filters = and(
+    and(
+      or(
+        gt(Interval::end_n(), *start),
+        eq(Interval::end_n(), FieldVal::Null),
+      ),
+      lt(Interval::begin_n(), *end),
+    ),
+    not(gt(Interval::deleted_n(), 0)),
+  );
+  if let Some(tag_filters) = tag_filters {
+    filters = and(
+      filters,
+      exists(from("nodes").filter(and(
+        eq(
+          Node::id_n(),
+          FieldVal::FieldName(Interval::node_id_n().into()),
+        ),
+        tag_filters,
+      ))),
+    );
+  }
+  Ok(ctx.db.get_by_statement(
+    filter(filters).sort(Interval::begin_n(), SortDir::Ascend),
+  )?)
+}
+
+/// Truncates intervals to the period bounds, running interval is
+/// closed at the period end.
+pub(crate) fn truncate_intervals(
+  intervals: &mut [Interval],
+  start: &DateTime<Utc>,
+  end: &DateTime<Utc>,
+) {
+  for interval in intervals.iter_mut() {
+    if interval.begin < *start {
+      interval.begin = start.to_owned();
+    }
+    if interval.end.is_none() || interval.end.unwrap() > *end {
+      interval.end = Some(end.to_owned());
+    }
+  }
+}
+
+/// Splits intervals at day bounds, so each part lies within a
+/// single day. Returns parts along with their days.
+pub(crate) fn split_by_days(
+  intervals: &[Interval],
+  opts: &PeriodOpts,
+) -> Vec<(Date<AppTz>, Interval)> {
+  let mut res = Vec::new();
+  for interval in intervals {
+    let end = interval.end.unwrap();
+    let mut begin = interval.begin;
+    while begin < end {
+      let day = opts.day_of(begin.with_timezone(&opts.tz));
+      let next = opts.day_begin(day.succ()).with_timezone(&Utc);
+      let part_end = end.min(next);
+      res.push((
+        day,
+        Interval {
+          begin,
+          end: Some(part_end),
+          ..*interval
+        },
+      ));
+      begin = part_end;
+    }
+  }
+
+  res
+}
+
+pub(crate) fn period_arg<'a>() -> Arg<'a, 'a> {
+  Arg::with_name("period")
+    .short("p")
+    .long("period")
+    .help("report period")
+    .takes_value(true)
+    .multiple(true)
+}
+
+pub(crate) fn format_arg<'a>() -> Arg<'a, 'a> {
+  Arg::with_name("format")
+    .short("f")
+    .long("format")
+    .help("output format")
+    .possible_values(&["markdown", "csv", "json", "html"])
+    .default_value("markdown")
+    .takes_value(true)
+}
+
+/// Prints report in format, given by "format" argument.
+pub(crate) fn print_report<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+  r: &Report,
+) {
+  match args.value_of("format") {
+    Some("csv") => ctx.printer.raw(&r.csv()),
+    Some("json") => ctx.printer.raw(&r.json()),
+    Some("html") => ctx.printer.raw(&r.html()),
+    _ => ctx.printer.report(r),
+  }
+}
+
+pub(crate) fn tags_arg<'a>() -> Arg<'a, 'a> {
+  Arg::with_name("tags")
+    .short("t")
+    .long("tags")
+    .help(
+      "comma separated tag list (use \"^\" before tag for negation)",
+    )
+    .takes_value(true)
+}
@@ -0,0 +1,140 @@
+use chrono::Duration;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap};
+
+use super::{
+  find_intervals, format_arg, get_period, matches_tags, period_arg,
+  print_report, tags_arg, tags_filter, truncate_intervals,
+};
+use crate::core::*;
+use crate::report::*;
+use crate::*;
+
+const UNTAGGED: &str = "(untagged)";
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let (start, end) = get_period(ctx, args)?;
+  let inherit = args.is_present("inherit");
+  // With inheritance, tags of ancestors are matched too, so the
+  // filter is applied after ancestors are loaded.
+  let tags = tags_filter(args);
+  let mut intervals = find_intervals(
+    ctx,
+    if inherit { &[] } else { &tags },
+    &start,
+    &end,
+  )?;
+  truncate_intervals(&mut intervals, &start, &end);
+  let combinations = args.is_present("combinations");
+
+  let mut paths: HashMap<usize, Vec<Node>> = HashMap::new();
+  for interval in intervals.iter() {
+    let id = interval.node_id.unwrap();
+    if let Entry::Vacant(e) = paths.entry(id) {
+      e.insert(ctx.db.ancestors(id)?);
+    }
+  }
+
+  let mut times: HashMap<String, Duration> = HashMap::new();
+  let mut total = Duration::zero();
+  for interval in intervals.iter() {
+    let path = &paths[&interval.node_id.unwrap()];
+    if path.iter().any(|n| n.deleted)
+      || inherit && !matches_tags(path, &tags)
+    {
+      continue;
+    }
+    let nodes = if inherit {
+      &path[..]
+    } else {
+      &path[path.len() - 1..]
+    };
+    let tags: BTreeSet<String> = nodes
+      .iter()
+      .flat_map(|n| n.get_tags())
+      .filter(|t| !t.is_empty())
+      .collect();
+
+    let time = interval.end.unwrap() - interval.begin;
+    total += time;
+    let keys: Vec<String> = if tags.is_empty() {
+      vec![UNTAGGED.to_string()]
+    } else if combinations {
+      vec![tags.into_iter().collect::<Vec<_>>().join(", ")]
+    } else {
+      tags.into_iter().collect()
+    };
+    for key in keys {
+      *times.entry(key).or_insert_with(Duration::zero) += time;
+    }
+  }
+
+  let mut times: Vec<(String, Duration)> =
+    times.into_iter().collect();
+  times.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+  let mut r = Report::new();
+  r.push(if combinations {
+    "Time by tag combinations."
+  } else {
+    "Time by tags."
+  });
+  r.push((start.with_timezone(&ctx.tz), end.with_timezone(&ctx.tz)));
+  r.push(vec![
+    if combinations { "Tags" } else { "Tag" }.to_string(),
+    "Time".to_string(),
+    "Share".to_string(),
+  ]);
+  let total_ms = total.num_milliseconds();
+  for (tag, time) in times {
+    let share = if total_ms > 0 {
+      time.num_milliseconds() * 100 / total_ms
+    } else {
+      0
+    };
+    r.push(vec![
+      Cell::String(tag),
+      Cell::Duration(time),
+      Cell::Percent(share.try_into().unwrap_or_default()),
+    ]);
+  }
+  if !total.is_zero() {
+    r.push(Row::Total(vec![Cell::Duration(total), Cell::Span]));
+  }
+
+  print_report(ctx, args, &r);
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("tags")
+      .about(
+        "Time per tag for period (default - current day).\n\
+        Time of tasks with several tags is counted for each of \
+        them, so shares could sum up to more than 100%. Time of \
+        tasks without tags is listed as \"(untagged)\".",
+      )
+      .arg(period_arg())
+      .arg(tags_arg())
+      .arg(format_arg())
+      .arg(
+        Arg::with_name("combinations")
+          .short("c")
+          .long("combinations")
+          .help(
+            "Group time by tag combinations instead of single tags",
+          ),
+      )
+      .arg(
+        Arg::with_name("inherit").short("i").long("inherit").help(
+          "Take tags of ancestor tasks into account, \
+            including the tags filter",
+        ),
+      ),
+  )
+}
@@ -0,0 +1,110 @@
+use chrono::Duration;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use super::{
+  format_arg, get_intervals, period_arg, print_report, split_by_days,
+  tags_arg,
+};
+use crate::core::*;
+use crate::parse::*;
+use crate::report::*;
+use crate::*;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let opts = PeriodOpts::new(&ctx.conf, ctx.tz)?;
+  let period = match args.values_of("period") {
+    Some(v) => v.collect::<Vec<_>>().join(" "),
+    None => "this-week".to_string(),
+  };
+  let (start, end) = parse_period(&period, &opts)?;
+  let intervals = get_intervals(ctx, args, &start, &end)?;
+
+  let mut days = Vec::new();
+  let mut day = opts.day_of(start.with_timezone(&opts.tz));
+  while opts.day_begin(day) < end {
+    days.push(day);
+    day = day.succ();
+  }
+
+  let mut paths: HashMap<usize, Vec<Node>> = HashMap::new();
+  for interval in intervals.iter() {
+    let id = interval.node_id.unwrap();
+    if let Entry::Vacant(e) = paths.entry(id) {
+      e.insert(ctx.db.ancestors(id)?);
+    }
+  }
+
+  // Time per task for each day of the period.
+  let mut grid: HashMap<usize, Vec<Duration>> = HashMap::new();
+  for (day, interval) in split_by_days(&intervals, &opts) {
+    let id = interval.node_id.unwrap();
+    if paths[&id].iter().any(|n| n.deleted) {
+      continue;
+    }
+    let col = days.iter().position(|d| *d == day).unwrap();
+    grid
+      .entry(id)
+      .or_insert_with(|| vec![Duration::zero(); days.len()])[col] +=
+      interval.end.unwrap() - interval.begin;
+  }
+  let mut tasks: Vec<(String, &Vec<Duration>)> = grid
+    .iter()
+    .map(|(id, row)| (format_task_name(&paths[id]), row))
+    .collect();
+  tasks.sort_by(|a, b| a.0.cmp(&b.0));
+
+  let mut r = Report::new();
+  r.push("Timesheet.");
+  r.push((start.with_timezone(&ctx.tz), end.with_timezone(&ctx.tz)));
+  let mut header = vec!["Task".to_string()];
+  header.extend(days.iter().map(|d| d.format("%a %d").to_string()));
+  header.push("Total".to_string());
+  r.push(header);
+
+  let mut totals = vec![Duration::zero(); days.len()];
+  for (name, row) in tasks {
+    let row_total =
+      row.iter().fold(Duration::zero(), |acc, v| acc + *v);
+    // Such rows would be blank.
+    if row_total.num_minutes() == 0 {
+      continue;
+    }
+    let mut cells = vec![Cell::String(name)];
+    for (i, time) in row.iter().enumerate() {
+      totals[i] += *time;
+      cells.push(Cell::Hours(*time));
+    }
+    cells.push(Cell::Hours(row_total));
+    r.push(cells);
+  }
+
+  let total = totals.iter().fold(Duration::zero(), |acc, v| acc + *v);
+  if !total.is_zero() {
+    let mut cells: Vec<Cell> =
+      totals.into_iter().map(Cell::Hours).collect();
+    cells.push(Cell::Hours(total));
+    r.push(Row::Total(cells));
+  }
+
+  print_report(ctx, args, &r);
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("timesheet")
+      .about(
+        "Time per task and day for period (default - current week).\n\
+        Tasks are listed down the side and days across the top, \
+        with totals per task and per day.",
+      )
+      .arg(period_arg())
+      .arg(tags_arg())
+      .arg(format_arg()),
+  )
+}
@@ -1,11 +1,12 @@
 use chrono::Duration;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 
+use super::{
+  format_arg, get_intervals, get_period, period_arg, print_report,
+  tags_arg,
+};
 use crate::core::*;
-use yatt_orm::statement::*;
-use yatt_orm::FieldVal;
-
-use crate::parse::*;
 use crate::report::*;
 use crate::*;
 
@@ -13,76 +14,8 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
   ctx: &AppContext<T, P>,
   args: &ArgMatches,
 ) -> CliResult<()> {
-  let (start, end) = if let Some(v) = args.values_of("period") {
-    parse_period(
-      &v.collect::<Vec<_>>().join(" "),
-      &PeriodOpts::default(),
-    )?
-  } else {
-    (Local::today().and_hms(0, 0, 0).into(), Local::now().into())
-  };
-  let tags = if let Some(tags) = args.value_of("tags") {
-    tags
-      .split(',')
-      .filter(|v| !v.is_empty())
-      .map(|v| v.to_lowercase())
-      .collect()
-  } else {
-    Vec::new()
-  };
-
-  let mut tag_filters = None;
-  for tag in tags {
-    let neg = tag.starts_with('^');
-    let tag = tag.trim_start_matches('^');
-    let fltr = if neg {
-      not(includes(Node::tags_n(), tag))
-    } else {
-      includes(Node::tags_n(), tag)
-    };
-
-    tag_filters = if let Some(prev_fltr) = tag_filters {
-      Some(and(prev_fltr, fltr))
-    } else {
-      Some(fltr)
-    }
-  }
-  let mut filters = and(
-    and(
-      or(
-        gt(Interval::end_n(), start),
-        eq(Interval::end_n(), FieldVal::Null),
-      ),
-      lt(Interval::begin_n(), end),
-    ),
-    not(gt(Interval::deleted_n(), 0)),
-  );
-  if let Some(tag_filters) = tag_filters {
-    filters = and(
-      filters,
-      exists(from("nodes").filter(and(
-        eq(
-          Node::id_n(),
-          FieldVal::FieldName(Interval::node_id_n().into()),
-        ),
-        tag_filters,
-      ))),
-    );
-  }
-  let mut intervals: Vec<Interval> = ctx.db.get_by_statement(
-    filter(filters).sort(Interval::begin_n(), SortDir::Ascend),
-  )?;
-  if !intervals.is_empty() {
-    if intervals[0].begin < start {
-      intervals[0].begin = start.to_owned();
-    }
-    let high = intervals.len() - 1;
-    if intervals[high].end.is_none()
-      || intervals[high].end.unwrap() > end
-    {
-      intervals[high].end = Some(end.to_owned());
-    }
-  }
+  let (start, end) = get_period(ctx, args)?;
+  let intervals = get_intervals(ctx, args, &start, &end)?;
 
   let ids = intervals.iter().fold(vec![], |mut acc, v| {
     if !acc.iter().any(|&n| n == v.node_id.unwrap()) {
@@ -123,9 +56,26 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
     res
   });
 
+  let mut budgets = HashMap::new();
+  for node in nodes.iter().flatten() {
+    if budgets.contains_key(&node.id) {
+      continue;
+    }
+    if let Some(estimate) = node.get_estimate() {
+      budgets.insert(node.id, (ctx.db.spent(node.id)?, estimate));
+    }
+  }
+
   let mut r = Report::new();
   r.push("Total time.");
-  r.push((start, end));
+  r.push((start.with_timezone(&ctx.tz), end.with_timezone(&ctx.tz)));
+  if !nodes.is_empty() {
+    let mut header = vec!["Task".to_string(), "Time".to_string()];
+    if !budgets.is_empty() {
+      header.push("Budget".to_string());
+    }
+    r.push(header);
+  }
   let mut old_path: &[Node] = &[];
   let mut sub_total = Duration::zero();
   let mut total = Duration::zero();
@@ -145,6 +95,7 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
           &node[i..],
           &mut r,
           &intervals,
+          &budgets,
           i,
           &mut sub_total,
           &mut total,
@@ -161,8 +112,9 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
   if !total.is_zero() {
     r.push(Row::Total(vec![Cell::Duration(total)]));
   }
+  push_pomodoros(&nodes, &intervals, &mut r);
 
-  ctx.printer.report(&r);
+  print_report(ctx, args, &r);
 
   Ok(())
 }
@@ -171,6 +123,7 @@ fn push_path(
   pth: &[Node],
   rep: &mut Report,
   ints: &[Interval],
+  budgets: &HashMap<usize, (Duration, Duration)>,
   pad: usize,
   sub_total: &mut Duration,
   total: &mut Duration,
@@ -201,6 +154,17 @@ fn push_path(
     if !wh.is_zero() {
       row.push(Cell::Duration(wh));
     }
+    if let Some((spent, estimate)) = budgets.get(&n.id) {
+      if wh.is_zero() {
+        row.push(Cell::Span);
+      }
+      let budget = format_budget(spent, estimate);
+      row.push(if !estimate.is_zero() && spent > estimate {
+        Cell::Warning(budget)
+      } else {
+        Cell::String(budget)
+      });
+    }
     if pad == 0 {
       rep.push(row);
     } else {
@@ -210,24 +174,61 @@ fn push_path(
   }
 }
 
+/// Adds section with completed pomodoros count per task.
+fn push_pomodoros(
+  nodes: &[Vec<Node>],
+  ints: &[Interval],
+  rep: &mut Report,
+) {
+  let mut total = 0;
+  for node in nodes {
+    let id = node.last().unwrap().id;
+    let cnt = ints
+      .iter()
+      .filter(|v| v.pomodoro && v.node_id == Some(id))
+      .count();
+    if cnt == 0 {
+      continue;
+    }
+    if total == 0 {
+      rep.push("Completed pomodoros.");
+      rep.push(vec!["Task".to_string(), "Pomodoros".to_string()]);
+    }
+    total += cnt;
+    rep.push(vec![
+      Cell::String(format_task_name(node)),
+      Cell::Usize(cnt),
+    ]);
+  }
+  if total > 0 {
+    rep.push(Row::Total(vec![Cell::Usize(total)]));
+  }
+}
+
+fn format_budget(spent: &Duration, estimate: &Duration) -> String {
+  let spent_str = if spent.is_zero() {
+    "nothing".to_string()
+  } else {
+    format_duration(spent)
+  };
+  if estimate.is_zero() {
+    return format!("{} spent, no budget", spent_str);
+  }
+  let pct = spent.num_seconds() * 100 / estimate.num_seconds();
+  format!("{} of {} ({}%)", spent_str, format_duration(estimate), pct)
+}
+
 pub fn register<'a>(app: App<'a, 'a>) -> App {
   app.subcommand(
     SubCommand::with_name("total")
-      .about("Total time for period (default - currernt day).")
-      .arg(
-        Arg::with_name("period")
-          .short("p")
-          .long("period")
-          .help("report period")
-          .takes_value(true)
-          .multiple(true),
+      .about(
+        "Total time for period (default - currernt day).\n\
+        For tasks with estimate, shows all time spent on the task\n\
+        and it's children against the estimate. Completed pomodoros\n\
+        are counted in a separate section.",
       )
-      .arg(
-        Arg::with_name("tags")
-          .short("t")
-          .long("tags")
-          .help("comma separated tag list (use \"^\" before tag for negation)")
-          .takes_value(true)
-      ),
+      .arg(period_arg())
+      .arg(tags_arg())
+      .arg(format_arg()),
   )
 }
@@ -1,4 +1,8 @@
+use crate::commands::{ago_arg, at_arg};
 use crate::core::{Interval, Node};
+use crate::parse::*;
+use crate::select::*;
+use crate::validate::*;
 use crate::*;
 use yatt_orm::statement::*;
 
@@ -23,12 +27,8 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
     });
   };
 
-  let node_id = if args.is_present("ID") {
-    args.value_of("ID").unwrap().parse().map_err(|_| {
-      CliError::Parse {
-        message: "Unable to parse task ID".into(),
-      }
-    })?
+  let node_id = if let Some(id) = args.value_of("ID") {
+    select_task(ctx.db, id)?.id
   } else {
     let interval: Vec<Interval> = ctx.db.get_by_statement(
       filter(and(
@@ -51,6 +51,7 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
 
   let mut interval = Interval::default();
   interval.node_id = Some(node_id);
+  interval.begin = parse_moment(args, &ctx.tz)?;
 
   let node: Vec<Node> = ctx
     .db
@@ -66,6 +67,7 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
 
   let node = node.first().unwrap();
 
+  check_interval(ctx.db, &interval)?;
   ctx.db.save(&interval)?;
 
   let task = &ctx.db.ancestors(node.id)?;
@@ -85,9 +87,12 @@ pub fn register<'a>(app: App<'a, 'a>) -> App {
   app.subcommand(
     SubCommand::with_name("restart")
       .about("Restarts last task")
-      .arg(
-        Arg::with_name("ID")
-          .help("Task id. By default, the last runned task is used"),
-      ),
+      .setting(AppSettings::AllowNegativeNumbers)
+      .arg(Arg::with_name("ID").help(
+        "Task id, path, \"last\", -N or name part. \
+          By default, the last runned task is used",
+      ))
+      .arg(at_arg())
+      .arg(ago_arg()),
   )
 }
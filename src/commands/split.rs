@@ -0,0 +1,112 @@
+use crate::core::*;
+use crate::parse::*;
+use crate::pick::*;
+use crate::select::*;
+use crate::*;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let mut first = select_interval(
+    ctx.db,
+    &value_or_pick(ctx, args, "ID", PickKind::Interval)?,
+  )?;
+
+  let at = args.value_of("AT").unwrap();
+  let at = if let Ok(at) = parse_date_time(at, &ctx.tz) {
+    at
+  } else {
+    first.begin + parse_duration(at)?
+  };
+
+  let end = first.end.unwrap_or_else(Utc::now);
+  if at <= first.begin || at >= end {
+    let task = ctx.db.ancestors(first.node_id.unwrap())?;
+    return Err(CliError::Task {
+      source: TaskError::CmdTaskInterval {
+        message: "Split point is out of the interval".to_string(),
+        interval: first,
+        task,
+      },
+    });
+  }
+
+  let node_id = if let Some(task) = args.value_of("task") {
+    let node = select_task(ctx.db, task)?;
+    node.id
+  } else {
+    first.node_id.unwrap()
+  };
+
+  let mut second = Interval {
+    id: 0,
+    node_id: Some(node_id),
+    begin: at,
+    end: first.end,
+    deleted: false,
+    closed: false,
+    pomodoro: false,
+    utc_offset: first.utc_offset,
+  };
+  first.end = Some(at);
+
+  ctx.db.save(&first)?;
+  second.id = ctx.db.save(&second)?;
+
+  let first_task = ctx.db.ancestors(first.node_id.unwrap())?;
+  let second_task = ctx.db.ancestors(node_id)?;
+  ctx.printer.interval_cmd(&IntervalCmdData {
+    cmd_text: "Interval is split.",
+    interval: IntervalData {
+      interval: &first,
+      task: &first_task,
+      title: "First part:",
+    },
+  });
+  ctx.printer.plain("");
+  ctx.printer.interval_cmd(&IntervalCmdData {
+    cmd_text: "",
+    interval: IntervalData {
+      interval: &second,
+      task: &second_task,
+      title: "Second part:",
+    },
+  });
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  lazy_static! {
+    static ref TASK_HELP: String =
+      format!("Task for the second part. {}", TASK_SELECTOR_HELP);
+  }
+  app.subcommand(
+    SubCommand::with_name("split")
+      .setting(AppSettings::AllowMissingPositional)
+      .setting(AppSettings::AllowNegativeNumbers)
+      .setting(AppSettings::ArgRequiredElseHelp)
+      .about("Splits an interval into two")
+      .arg(
+        Arg::with_name("ID")
+          .help("[ID] or -[offset] from NOW (starting with -1)"),
+      )
+      .arg(
+        Arg::with_name("AT")
+          .help(
+            "Split point: date and time, or duration \
+            from the interval begin",
+          )
+          .required(true),
+      )
+      .arg(
+        Arg::with_name("task")
+          .short("t")
+          .long("task")
+          .help(&TASK_HELP)
+          .allow_hyphen_values(true)
+          .takes_value(true),
+      ),
+  )
+}
@@ -1,4 +1,7 @@
-use crate::core::Interval;
+use crate::commands::{ago_arg, at_arg};
+use crate::core::{local_offset, Interval};
+use crate::parse::*;
+use crate::validate::*;
 use crate::*;
 
 pub(crate) fn exec<T: DBRoot, P: Printer>(
@@ -24,15 +27,19 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
   let path = path.join(" ");
   let path: Vec<&str> = path.split("::").map(|t| t.trim()).collect();
 
+  let begin = parse_moment(args, &ctx.tz)?;
   let nodes = ctx.db.create_path(&path)?;
   let interval = Interval {
     id: 0,
     node_id: Some(nodes.last().unwrap().id),
-    begin: Utc::now(),
+    begin,
     end: None,
     deleted: false,
     closed: false,
+    pomodoro: false,
+    utc_offset: Some(local_offset(begin)),
   };
+  check_interval(ctx.db, &interval)?;
   ctx.db.save(&interval)?;
 
   ctx.printer.interval_cmd(&IntervalCmdData {
@@ -58,6 +65,8 @@ pub fn register<'a>(app: App<'a, 'a>) -> App {
           .help("Task name with nested tasks, delimited by \"::\"")
           .required(true)
           .multiple(true),
-      ),
+      )
+      .arg(at_arg())
+      .arg(ago_arg()),
   )
 }
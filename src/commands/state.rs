@@ -1,4 +1,4 @@
-use crate::*;
+use crate::{core::Node, *};
 
 pub(crate) fn exec<T: DBRoot, P: Printer>(
   ctx: &AppContext<T, P>,
@@ -19,6 +19,7 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
         title: IntervalData::default_title(),
       },
     });
+    warn_over_budget(ctx, task)?;
   } else {
     let last = ctx.db.last_running()?;
     let cmd_text = &"Stopped";
@@ -40,6 +41,27 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
   Ok(())
 }
 
+fn warn_over_budget<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  task: &[Node],
+) -> CliResult<()> {
+  for (i, node) in task.iter().enumerate() {
+    if let Some(estimate) = node.get_estimate() {
+      let spent = ctx.db.spent(node.id)?;
+      if spent > estimate {
+        ctx.printer.warning(&format!(
+          "task \"{}\" is over budget: {} spent of {} estimated",
+          format_task_name(&task[..=i]),
+          format_duration(&spent),
+          format_duration(&estimate),
+        ));
+      }
+    }
+  }
+
+  Ok(())
+}
+
 pub fn register<'a>(app: App<'a, 'a>) -> App {
   app.subcommand(
     SubCommand::with_name("state")
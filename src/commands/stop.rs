@@ -1,8 +1,11 @@
+use crate::commands::{ago_arg, at_arg};
+use crate::parse::*;
+use crate::validate::*;
 use crate::*;
 
 pub(crate) fn exec<T: DBRoot, P: Printer>(
   ctx: &AppContext<T, P>,
-  _args: &ArgMatches,
+  args: &ArgMatches,
 ) -> CliResult<()> {
   let res = ctx
     .db
@@ -19,7 +22,8 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
 
   let (node, mut interval) = res.unwrap();
 
-  interval.end = Some(Utc::now());
+  interval.end = Some(parse_moment(args, &ctx.tz)?);
+  check_interval(ctx.db, &interval)?;
   ctx.db.save(&interval)?;
 
   let task = &ctx.db.ancestors(node.id)?;
@@ -38,6 +42,9 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
 
 pub fn register<'a>(app: App<'a, 'a>) -> App {
   app.subcommand(
-    SubCommand::with_name("stop").about("Stops running task"),
+    SubCommand::with_name("stop")
+      .about("Stops running task")
+      .arg(at_arg())
+      .arg(ago_arg()),
   )
 }
@@ -0,0 +1,100 @@
+use crate::commands::{ago_arg, at_arg};
+use crate::core::{local_offset, Interval};
+use crate::parse::*;
+use crate::validate::*;
+use crate::*;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let res = ctx
+    .db
+    .cur_running()
+    .map_err(|source| CliError::DB { source })?;
+
+  if res.is_none() {
+    return Err(CliError::Task {
+      source: TaskError::Cmd {
+        message: "No task running.".to_string(),
+      },
+    });
+  }
+
+  let (node, mut stopped) = res.unwrap();
+
+  let path: Vec<&str> = args.values_of("TASK").unwrap().collect();
+  let path = path.join(" ");
+  let path: Vec<&str> = path.split("::").map(|t| t.trim()).collect();
+
+  let nodes = ctx.db.create_path(&path)?;
+  if nodes.last().unwrap().id == node.id {
+    let task = ctx.db.ancestors(node.id)?;
+    return Err(CliError::Task {
+      source: TaskError::CmdTaskInterval {
+        message: "Task already running.".to_string(),
+        interval: stopped,
+        task,
+      },
+    });
+  }
+
+  let at = parse_moment(args, &ctx.tz)?;
+  stopped.end = Some(at);
+  check_interval(ctx.db, &stopped)?;
+  ctx.db.save(&stopped)?;
+
+  let started = Interval {
+    id: 0,
+    node_id: Some(nodes.last().unwrap().id),
+    begin: at,
+    end: None,
+    deleted: false,
+    closed: false,
+    pomodoro: false,
+    utc_offset: Some(local_offset(at)),
+  };
+  check_interval(ctx.db, &started)?;
+  ctx.db.save(&started)?;
+
+  let task = &ctx.db.ancestors(node.id)?;
+  ctx.printer.interval_cmd(&IntervalCmdData {
+    cmd_text: "Stopping...",
+    interval: IntervalData {
+      interval: &stopped,
+      task,
+      title: IntervalData::default_title(),
+    },
+  });
+  ctx.printer.plain("");
+  ctx.printer.interval_cmd(&IntervalCmdData {
+    cmd_text: "Starting...",
+    interval: IntervalData {
+      interval: &started,
+      task: &nodes,
+      title: IntervalData::default_title(),
+    },
+  });
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("switch")
+      .alias("sw")
+      .about(
+        "Stops running task and starts another one \
+        at the same moment",
+      )
+      .setting(AppSettings::ArgRequiredElseHelp)
+      .arg(
+        Arg::with_name("TASK")
+          .help("Task name with nested tasks, delimited by \"::\"")
+          .required(true)
+          .multiple(true),
+      )
+      .arg(at_arg())
+      .arg(ago_arg()),
+  )
+}
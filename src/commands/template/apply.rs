@@ -0,0 +1,121 @@
+use super::{parse_template, template_path};
+use crate::core::*;
+use crate::*;
+use yatt_orm::statement::*;
+use yatt_orm::FieldVal;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let name = args.value_of("NAME").unwrap();
+  let file = template_path(ctx, name)?;
+  if !file.is_file() {
+    return Err(CliError::Cmd {
+      message: format!("No such template \"{}\"", name),
+    });
+  }
+  let text = fs::read_to_string(&file)
+    .map_err(|source| CliError::Io { source })?;
+  let template = parse_template(&text)?;
+
+  let root_label =
+    args.value_of("label").unwrap_or(&template[0].label);
+  let parent = args
+    .values_of("PARENT")
+    .map(|v| v.collect::<Vec<&str>>().join(" "))
+    .unwrap_or_default();
+  let mut path: Vec<&str> = parent
+    .split("::")
+    .map(|t| t.trim())
+    .filter(|t| !t.is_empty())
+    .collect();
+
+  let parent_id = if path.is_empty() {
+    None
+  } else {
+    ctx.db.create_path(&path)?.last().map(|n| n.id)
+  };
+  let siblings: Vec<Node> = ctx.db.get_by_filter(and(
+    if let Some(parent_id) = parent_id {
+      eq(Node::parent_id_n(), parent_id)
+    } else {
+      eq(Node::parent_id_n(), FieldVal::Null)
+    },
+    ne(Node::deleted_n(), 1),
+  ))?;
+  if siblings.iter().any(|n| n.label == root_label) {
+    return Err(CliError::Cmd {
+      message: format!(
+        "Task \"{}\" already exists there, use --label to set \
+        another label",
+        root_label
+      ),
+    });
+  }
+
+  let base_len = path.len();
+  let mut root = Vec::new();
+  for node in template.iter() {
+    path.truncate(base_len + node.depth);
+    path.push(if node.depth == 0 {
+      root_label
+    } else {
+      &node.label
+    });
+    let mut nodes = ctx.db.create_path(&path)?;
+    let last = nodes.last_mut().unwrap();
+    if !node.tags.is_empty() {
+      last.add_tags(&node.tags);
+      ctx.db.save(last)?;
+    }
+    if node.depth == 0 {
+      root = nodes;
+    }
+  }
+
+  ctx.printer.node_cmd(&NodeCmdData {
+    cmd_text: &format!(
+      "Template \"{}\" applied, {} task(s) in template.",
+      name,
+      template.len()
+    ),
+    node: NodeData {
+      title: NodeData::default_title(),
+      node: &root,
+    },
+  });
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("apply")
+      .setting(AppSettings::ArgRequiredElseHelp)
+      .about(
+        "Creates a task tree from a template under the given task\n\
+        The template root becomes a subtask of PARENT, which is \
+        created, if it doesn't exist. With no PARENT the tree is \
+        created at the top level.",
+      )
+      .arg(
+        Arg::with_name("NAME").help("Template name").required(true),
+      )
+      .arg(
+        Arg::with_name("PARENT")
+          .help(
+            "Parent task name with nested tasks, delimited by \
+            \"::\"",
+          )
+          .multiple(true),
+      )
+      .arg(
+        Arg::with_name("label")
+          .short("l")
+          .long("label")
+          .takes_value(true)
+          .help("Label of the created root, template root label by default"),
+      ),
+  )
+}
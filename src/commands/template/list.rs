@@ -0,0 +1,37 @@
+use super::templates_dir;
+use crate::*;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  _args: &ArgMatches,
+) -> CliResult<()> {
+  let dir = templates_dir(ctx);
+  let mut names = Vec::new();
+  if dir.is_dir() {
+    for entry in
+      fs::read_dir(&dir).map_err(|source| CliError::Io { source })?
+    {
+      let entry = entry.map_err(|source| CliError::Io { source })?;
+      if entry.path().is_file() {
+        names.push(entry.file_name().to_string_lossy().to_string());
+      }
+    }
+  }
+  if names.is_empty() {
+    ctx.printer.plain("There is no templates saved.");
+    return Ok(());
+  }
+
+  names.sort();
+  for name in names {
+    ctx.printer.plain(&name);
+  }
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("list").about("List saved templates"),
+  )
+}
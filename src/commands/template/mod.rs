@@ -0,0 +1,160 @@
+use crate::core::*;
+use crate::*;
+use yatt_orm::statement::*;
+
+mod apply;
+mod list;
+mod root;
+mod save;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  match args.subcommand() {
+    ("save", Some(m)) => save::exec(ctx, m),
+    ("apply", Some(m)) => apply::exec(ctx, m),
+    ("list", Some(m)) => list::exec(ctx, m),
+    _ => root::exec(ctx, args),
+  }
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  let sub = SubCommand::with_name("template")
+    .setting(AppSettings::ArgRequiredElseHelp)
+    .about("Task tree templates managing");
+  let sub = root::register(sub);
+  let sub = save::register(sub);
+  let sub = apply::register(sub);
+  let sub = list::register(sub);
+
+  app.subcommand(sub)
+}
+
+/// Node of a template, depth is counted from the template root.
+pub(crate) struct TemplateNode {
+  pub depth: usize,
+  pub label: String,
+  pub tags: Vec<String>,
+}
+
+/// Returns given node with all not deleted descendants in
+/// depth-first order, along with depth of each of them.
+pub(crate) fn collect_subtree<T: DBRoot>(
+  db: &T,
+  node: Node,
+  depth: usize,
+  res: &mut Vec<(usize, Node)>,
+) -> CliResult<()> {
+  let children: Vec<Node> = db.get_by_statement(
+    filter(and(
+      eq(Node::parent_id_n(), node.id),
+      ne(Node::deleted_n(), 1),
+    ))
+    .sort(Node::id_n(), SortDir::Ascend),
+  )?;
+  res.push((depth, node));
+  for child in children {
+    collect_subtree(db, child, depth + 1, res)?;
+  }
+
+  Ok(())
+}
+
+fn templates_dir<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+) -> PathBuf {
+  ctx.root.join("templates")
+}
+
+fn template_path<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  name: &str,
+) -> CliResult<PathBuf> {
+  if name.is_empty()
+    || name.starts_with('.')
+    || name.contains(['/', '\\'])
+  {
+    return Err(CliError::Cmd {
+      message: format!("Invalid template name \"{}\"", name),
+    });
+  }
+
+  Ok(templates_dir(ctx).join(name))
+}
+
+/// Template is stored as an outline, one task per line, indented
+/// by two spaces per level. Tags follow the label after a tab.
+fn format_template(nodes: &[(usize, Node)]) -> String {
+  let mut res = String::new();
+  for (depth, node) in nodes {
+    res += &"  ".repeat(*depth);
+    res += &node.label;
+    let tags: Vec<String> = node
+      .get_tags()
+      .into_iter()
+      .filter(|t| !t.is_empty())
+      .collect();
+    if !tags.is_empty() {
+      res += "\t";
+      res += &tags.join(",");
+    }
+    res += "\n";
+  }
+
+  res
+}
+
+fn parse_template(text: &str) -> CliResult<Vec<TemplateNode>> {
+  let mut res: Vec<TemplateNode> = Vec::new();
+  for (n, line) in text.lines().enumerate() {
+    if line.trim().is_empty() {
+      continue;
+    }
+    let err = |message: &str| CliError::Parse {
+      message: format!("{} at line {} of template", message, n + 1),
+    };
+
+    let label = line.trim_start_matches(' ');
+    let indent = line.len() - label.len();
+    if indent % 2 != 0 {
+      return Err(err("Odd indentation"));
+    }
+    let depth = indent / 2;
+    match res.last() {
+      None if depth > 0 => {
+        return Err(err("First task should not be indented"))
+      }
+      Some(_) if depth == 0 => {
+        return Err(err("Template should have single root task"))
+      }
+      Some(prev) if depth > prev.depth + 1 => {
+        return Err(err("Too deep indentation"))
+      }
+      _ => {}
+    }
+
+    let (label, tags) = label.split_once('\t').unwrap_or((label, ""));
+    let label = label.trim();
+    if label.is_empty() {
+      return Err(err("Empty task label"));
+    }
+    res.push(TemplateNode {
+      depth,
+      label: label.to_string(),
+      tags: tags
+        .split(',')
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect(),
+    });
+  }
+
+  if res.is_empty() {
+    return Err(CliError::Parse {
+      message: "Template is empty".into(),
+    });
+  }
+
+  Ok(res)
+}
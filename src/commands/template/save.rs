@@ -0,0 +1,66 @@
+use super::{collect_subtree, format_template, template_path};
+use crate::pick::*;
+use crate::select::*;
+use crate::*;
+
+pub(crate) fn exec<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+) -> CliResult<()> {
+  let node = select_task(
+    ctx.db,
+    &value_or_pick(ctx, args, "ID", PickKind::Task)?,
+  )?;
+  let name = args.value_of("NAME").unwrap();
+  let path = template_path(ctx, name)?;
+  if path.exists() && !args.is_present("force") {
+    return Err(CliError::Cmd {
+      message: format!(
+        "Template \"{}\" already exists, use --force to replace it",
+        name
+      ),
+    });
+  }
+
+  let task = ctx.db.ancestors(node.id)?;
+  let mut nodes = Vec::new();
+  collect_subtree(ctx.db, node, 0, &mut nodes)?;
+
+  fs::create_dir_all(path.parent().unwrap())
+    .map_err(|source| CliError::Io { source })?;
+  fs::write(&path, format_template(&nodes))
+    .map_err(|source| CliError::Io { source })?;
+
+  ctx.printer.node_cmd(&NodeCmdData {
+    cmd_text: &format!(
+      "Template \"{}\" saved with {} task(s).",
+      name,
+      nodes.len()
+    ),
+    node: NodeData {
+      title: NodeData::default_title(),
+      node: &task,
+    },
+  });
+
+  Ok(())
+}
+
+pub fn register<'a>(app: App<'a, 'a>) -> App {
+  app.subcommand(
+    SubCommand::with_name("save")
+      .setting(AppSettings::AllowMissingPositional)
+      .setting(AppSettings::AllowNegativeNumbers)
+      .about("Saves task with all subtasks and tags as a template")
+      .arg(Arg::with_name("ID").help(TASK_SELECTOR_HELP))
+      .arg(
+        Arg::with_name("NAME").help("Template name").required(true),
+      )
+      .arg(
+        Arg::with_name("force")
+          .short("f")
+          .long("force")
+          .help("Replace existing template"),
+      ),
+  )
+}
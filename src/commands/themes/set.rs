@@ -1,4 +1,5 @@
 use crate::core::*;
+use crate::pick::*;
 
 use crate::*;
 
@@ -6,7 +7,7 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
   ctx: &AppContext<T, P>,
   args: &ArgMatches,
 ) -> CliResult<()> {
-  let theme = args.value_of("THEME").unwrap();
+  let theme = &value_or_pick(ctx, args, "THEME", PickKind::Theme)?;
   let file_path = ctx.root.join("themes").join(theme);
   if !file_path.is_file() {
     return Err(CliError::Cmd {
@@ -27,7 +28,7 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
 pub fn register<'a>(app: App<'a, 'a>) -> App {
   app.subcommand(
     SubCommand::with_name("set")
-      .arg(Arg::with_name("THEME").help("Theme name").required(true))
+      .arg(Arg::with_name("THEME").help("Theme name"))
       .about("Set current theme"),
   )
 }
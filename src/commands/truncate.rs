@@ -1,3 +1,4 @@
+use crate::select::*;
 use crate::{core::*, parse::*, *};
 use yatt_orm::statement::*;
 
@@ -6,19 +7,19 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
   args: &ArgMatches,
 ) -> CliResult<()> {
   let duration = parse_duration(args.value_of("DURATION").unwrap())?;
-  let task: Option<usize> = if let Some(t) = args.value_of("task") {
-    Some(t.parse().map_err(|e| CliError::wrap(Box::new(e)))?)
+  let task = if let Some(t) = args.value_of("task") {
+    Some(select_task(ctx.db, t)?)
   } else {
     None
   };
 
-  let (node, interval) = if let Some(id) = task {
-    let node: Node = ctx.db.get_by_id(id)?;
+  let (node, interval) = if let Some(node) = task {
     let mut interval: Vec<Interval> = ctx.db.get_by_statement(
       filter(and(
-        eq(Interval::id_n(), node.id),
-        eq(Interval::deleted_n(), 0),
+        eq(Interval::node_id_n(), node.id),
+        ne(Interval::deleted_n(), 1),
       ))
+      .sort(Interval::begin_n(), SortDir::Descend)
       .limit(1),
     )?;
     if interval.is_empty() {
@@ -63,6 +64,8 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
     deleted: false,
     closed: false,
     node_id: interval.node_id,
+    pomodoro: interval.pomodoro,
+    utc_offset: interval.utc_offset,
   };
 
   ctx
@@ -95,14 +98,15 @@ pub fn register<'a>(app: App<'a, 'a>) -> App {
       .setting(AppSettings::ArgRequiredElseHelp)
       .arg(
         Arg::with_name("DURATION")
-          .help("Truncate duration")
+          .help("Truncate duration (e.g. 30m, 1h30m, 1.5h or 01:30)")
           .required(true),
       )
       .arg(
         Arg::with_name("task")
           .short("t")
           .long("task")
-          .help("Task id")
+          .help(TASK_SELECTOR_HELP)
+          .allow_hyphen_values(true)
           .takes_value(true)
           .multiple(true),
       ),
@@ -1,36 +1,15 @@
-use yatt_orm::DBError;
-
+use crate::pick::*;
+use crate::select::*;
 use crate::{core::Node, *};
 
 pub(crate) fn exec<T: DBRoot, P: Printer>(
   ctx: &AppContext<T, P>,
   args: &ArgMatches,
 ) -> CliResult<()> {
-  let (ids, tags) = parse_args(args)?;
+  let (nodes, tags) = parse_args(ctx, args)?;
 
   let mut updated: Vec<Node> = Vec::new();
-  for id in ids {
-    let mut node = if id == 0 {
-      if let Some((node, _)) = ctx.db.cur_running()? {
-        node
-      } else {
-        return Err(CliError::Task {
-          source: TaskError::Cmd {
-            message: "No task running.".to_string(),
-          },
-        });
-      }
-    } else {
-      ctx.db.get_by_id(id).map_err(|source| match source {
-        DBError::IsEmpty { .. } => CliError::Task {
-          source: TaskError::Cmd {
-            message: "No task running".to_string(),
-          },
-        },
-        _ => CliError::DB { source },
-      })?
-    };
-
+  for mut node in nodes {
     node.remove_tags(&tags);
     ctx.db.save(&node)?;
     updated.push(node);
@@ -56,32 +35,14 @@ pub(crate) fn exec<T: DBRoot, P: Printer>(
   Ok(())
 }
 
-pub(crate) fn parse_args(
+pub(crate) fn parse_args<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
   args: &ArgMatches,
-) -> CliResult<(Vec<usize>, Vec<String>)> {
-  let id = args.value_of("ID").unwrap().to_lowercase();
-  let ids: Vec<usize> = if id == "cur" || id == "current" {
-    vec![0]
-  } else {
-    let parsed: Vec<Result<_, _>> = id
-      .split(',')
-      .map(|v| v.trim())
-      .filter(|v| !v.is_empty())
-      .map(|v| v.parse())
-      .collect();
-    if parsed.iter().any(|v| v.is_err()) {
-      return Err(CliError::Parse {
-        message: "Unable to parse task ID".into(),
-      });
-    }
-    let mut res = Vec::new();
-
-    for v in parsed {
-      res.push(v.unwrap());
-    }
-
-    res
-  };
+) -> CliResult<(Vec<Node>, Vec<String>)> {
+  let nodes = select_tasks(
+    ctx.db,
+    &value_or_pick(ctx, args, "ID", PickKind::Task)?,
+  )?;
   let tags: Vec<String> = args
     .value_of("TAGS")
     .unwrap()
@@ -89,19 +50,17 @@ pub(crate) fn parse_args(
     .map(|s| s.trim().to_lowercase())
     .filter(|s| !s.is_empty())
     .collect();
-  Ok((ids, tags))
+  Ok((nodes, tags))
 }
 
 pub fn register<'a>(app: App<'a, 'a>) -> App {
   app.subcommand(
     SubCommand::with_name("untag")
+      .setting(AppSettings::AllowMissingPositional)
+      .setting(AppSettings::AllowNegativeNumbers)
       .about("Removes comma separated tags from task")
       .setting(AppSettings::ArgRequiredElseHelp)
-      .arg(
-        Arg::with_name("ID")
-          .help("Task id or \"cur[rent]\" (for current running task)")
-          .required(true),
-      )
+      .arg(Arg::with_name("ID").help(TASKS_SELECTOR_HELP))
       .arg(
         Arg::with_name("TAGS")
           .help("Comma separated tags list")
@@ -3,6 +3,8 @@ use std::collections::hash_map::RandomState;
 use std::collections::HashSet;
 
 use chrono::prelude::*;
+use chrono::Duration;
+use std::convert::TryInto;
 use std::error::Error;
 use trees::{tr, Forest, ForestWalk, Visit};
 use yatt_orm::errors::{DBError, DBResult};
@@ -338,6 +340,182 @@ pub trait DBRoot: Storage {
         == 1,
     )
   }
+
+  /// Returns not deleted intervals, which intersects with given
+  /// one. Interval with no end is considered as running till now.
+  fn overlapping(
+    &self,
+    interval: &Interval,
+  ) -> DBResult<Vec<Interval>>
+  where
+    Self: Sized,
+  {
+    let mut filt = and(
+      and(
+        ne(Interval::deleted_n(), 1),
+        ne(Interval::id_n(), interval.id),
+      ),
+      or(
+        eq(Interval::end_n(), FieldVal::Null),
+        gt(Interval::end_n(), interval.begin),
+      ),
+    );
+    if let Some(end) = interval.end {
+      filt = and(filt, lt(Interval::begin_n(), end));
+    }
+
+    self.get_by_statement(
+      filter(filt).sort(Interval::begin_n(), SortDir::Ascend),
+    )
+  }
+
+  /// Returns total time spent on node with given id and all
+  /// it's children. Running interval is counted up to now.
+  fn spent(&self, node_id: usize) -> DBResult<Duration>
+  where
+    Self: Sized,
+  {
+    let now = Utc::now();
+    let mut res = self
+      .get_by_filter::<Interval>(and(
+        eq(Interval::node_id_n(), node_id),
+        ne(Interval::deleted_n(), 1),
+      ))?
+      .iter()
+      .fold(Duration::zero(), |acc, v| {
+        acc + (v.end.unwrap_or(now) - v.begin)
+      });
+
+    let children: Vec<Node> = self.get_by_filter(and(
+      eq(Node::parent_id_n(), node_id),
+      ne(Node::deleted_n(), 1),
+    ))?;
+    for node in children {
+      res += self.spent(node.id)?;
+    }
+
+    Ok(res)
+  }
+
+  /// Lists steps of merging node `from` into node `to`: `from`
+  /// is merged first, then it's children, which have a sibling
+  /// with the same label under the target, are merged
+  /// recursively, the rest of them are moved.
+  fn merge_plan(
+    &self,
+    from: &Node,
+    to: &Node,
+  ) -> DBResult<Vec<MergeStep>>
+  where
+    Self: Sized,
+  {
+    let mut steps = Vec::new();
+    self.collect_merge_steps(from, to, 0, &mut steps)?;
+    Ok(steps)
+  }
+
+  fn collect_merge_steps(
+    &self,
+    from: &Node,
+    to: &Node,
+    depth: usize,
+    steps: &mut Vec<MergeStep>,
+  ) -> DBResult<()>
+  where
+    Self: Sized,
+  {
+    steps.push(MergeStep::Merge {
+      from: from.clone(),
+      to: to.clone(),
+      depth,
+    });
+
+    let targets: Vec<Node> =
+      self.get_by_filter(eq(Node::parent_id_n(), to.id))?;
+    for child in
+      self.get_by_filter::<Node>(eq(Node::parent_id_n(), from.id))?
+    {
+      let target = targets
+        .iter()
+        .filter(|n| n.label == child.label)
+        .min_by_key(|n| (n.deleted, n.id));
+      if let Some(target) = target {
+        self.collect_merge_steps(&child, target, depth + 1, steps)?;
+      } else {
+        steps.push(MergeStep::Move {
+          node: child,
+          to: to.clone(),
+          depth: depth + 1,
+        });
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Merges node `from` into node `to`: intervals are moved,
+  /// children with matching labels are merged recursively, the
+  /// rest of children are moved, tags are united. Source node
+  /// is marked as deleted.
+  fn merge_node(&self, from: &Node, to: &Node) -> DBResult<()>
+  where
+    Self: Sized,
+  {
+    for step in self.merge_plan(from, to)? {
+      match step {
+        MergeStep::Merge { from, to, .. } => {
+          self.merge_single_node(&from, &to)?
+        }
+        MergeStep::Move { node, to, .. } => {
+          let mut node: Node = self.get_by_id(node.id)?;
+          node.parent_id = Some(to.id);
+          self.save(&node)?;
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  fn merge_single_node(&self, from: &Node, to: &Node) -> DBResult<()>
+  where
+    Self: Sized,
+  {
+    for mut interval in self
+      .get_by_filter::<Interval>(eq(Interval::node_id_n(), from.id))?
+    {
+      interval.node_id = Some(to.id);
+      self.save(&interval)?;
+    }
+
+    let mut to: Node = self.get_by_id(to.id)?;
+    let tags: Vec<String> = from
+      .get_tags()
+      .into_iter()
+      .chain(to.get_tags())
+      .filter(|t| !t.is_empty())
+      .collect();
+    if !tags.is_empty() {
+      to.set_tags(&tags);
+    }
+    to.deleted = to.deleted && from.deleted;
+    self.save(&to)?;
+
+    let mut from: Node = self.get_by_id(from.id)?;
+    from.deleted = true;
+    self.save(&from)?;
+
+    Ok(())
+  }
+}
+
+/// Single step of `DBRoot::merge_plan`.
+#[derive(Debug, Clone)]
+pub enum MergeStep {
+  /// Intervals and tags of `from` go to `to`, `from` is deleted.
+  Merge { from: Node, to: Node, depth: usize },
+  /// `node` is moved under `to` with it's subtree.
+  Move { node: Node, to: Node, depth: usize },
 }
 
 fn adopt_node(
@@ -434,6 +612,9 @@ pub struct Node {
   pub closed: bool,
   pub deleted: bool,
   pub tags: String,
+  pub estimate: Option<usize>,
+  pub rate: Option<usize>,
+  pub currency: String,
 }
 
 impl PartialOrd for Node {
@@ -452,6 +633,9 @@ impl Default for Node {
       closed: false,
       deleted: false,
       tags: String::new(),
+      estimate: None,
+      rate: None,
+      currency: String::new(),
     }
   }
 }
@@ -498,6 +682,49 @@ impl Node {
   pub fn get_comma_tags(&self) -> Vec<String> {
     self.get_tags().iter().map(|v| format!(",{},", v)).collect()
   }
+  pub fn get_estimate(&self) -> Option<Duration> {
+    self
+      .estimate
+      .map(|v| Duration::seconds(v.try_into().unwrap_or(i64::MAX)))
+  }
+  pub fn set_estimate(&mut self, estimate: Option<Duration>) {
+    self.estimate =
+      estimate.map(|v| v.num_seconds().try_into().unwrap_or(0));
+  }
+  pub fn get_rate(&self) -> Option<Rate> {
+    self.rate.map(|amount| Rate {
+      amount,
+      currency: self.currency.to_owned(),
+    })
+  }
+  pub fn set_rate(&mut self, rate: Option<Rate>) {
+    if let Some(rate) = rate {
+      self.rate = Some(rate.amount);
+      self.currency = rate.currency;
+    } else {
+      self.rate = None;
+      self.currency = String::new();
+    }
+  }
+}
+
+/// Hourly rate, amount is in hundredths of currency unit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rate {
+  pub amount: usize,
+  pub currency: String,
+}
+
+impl Rate {
+  /// Returns cost of given duration in hundredths
+  /// of currency unit.
+  pub fn cost(&self, d: &Duration) -> usize {
+    let secs: u128 = d.num_seconds().max(0).try_into().unwrap();
+    let amount: u128 = self.amount.try_into().unwrap();
+    ((amount * secs + 1800) / 3600)
+      .try_into()
+      .unwrap_or(usize::MAX)
+  }
 }
 
 #[derive(Debug, Clone, Copy, Identifiers)]
@@ -508,17 +735,25 @@ pub struct Interval {
   pub end: Option<DateTime<Utc>>,
   pub deleted: bool,
   pub closed: bool,
+  /// Interval is a completed pomodoro work block.
+  pub pomodoro: bool,
+  /// UTC offset in seconds of the timezone, interval was
+  /// recorded in.
+  pub utc_offset: Option<i32>,
 }
 
 impl Default for Interval {
   fn default() -> Self {
+    let begin = Utc::now();
     Interval {
       id: 0,
       node_id: None,
-      begin: Utc::now(),
+      begin,
       end: None,
       deleted: false,
       closed: false,
+      pomodoro: false,
+      utc_offset: Some(local_offset(begin)),
     }
   }
 }
@@ -533,3 +768,10 @@ impl ToString for Interval {
 }
 
 impl DBRoot for DB<'_> {}
+
+/// Returns UTC offset in seconds of the system timezone at the
+/// given moment. It's kept with intervals to tell, where they
+/// were actually recorded.
+pub fn local_offset(at: DateTime<Utc>) -> i32 {
+  at.with_timezone(&Local).offset().local_minus_utc()
+}
@@ -1,7 +1,8 @@
 use chrono::prelude::*;
 use chrono::Duration;
 
-use crate::core::Node;
+use crate::core::{Node, Rate};
+use crate::AppTz;
 
 pub(crate) fn format_task_name(t: &[Node]) -> String {
   t.iter()
@@ -16,21 +17,27 @@ pub struct DateTimeOpts {
   pub no_string_now: bool,
 }
 
-pub(crate) fn format_datetime(dt: &DateTime<Utc>) -> String {
-  format_datetime_opts(dt, &DateTimeOpts::default())
+pub(crate) fn format_datetime(
+  dt: &DateTime<Utc>,
+  tz: &AppTz,
+) -> String {
+  format_datetime_opts(
+    &dt.with_timezone(tz),
+    &DateTimeOpts::default(),
+  )
 }
 
 pub(crate) fn format_datetime_opts(
-  dt: &DateTime<Utc>,
+  dt: &DateTime<AppTz>,
   opts: &DateTimeOpts,
 ) -> String {
-  let dt: DateTime<Local> = DateTime::from(*dt);
-  let delta = Local::now() - dt;
+  let now = Utc::now().with_timezone(&dt.timezone());
+  let delta = now - *dt;
 
   let pattern = if delta < Duration::seconds(2) && !opts.no_string_now
   {
     "just now"
-  } else if dt.date() == Local::today() && !opts.always_long {
+  } else if dt.date() == now.date() && !opts.always_long {
     "%H:%M:%S"
   } else {
     "%Y-%m-%d %H:%M:%S"
@@ -80,6 +87,25 @@ pub(crate) fn format_duration(dur: &Duration) -> String {
   }
 }
 
+/// Formats duration with explicit sign, e.g. "-1 hour".
+pub(crate) fn format_duration_signed(dur: &Duration) -> String {
+  if dur.num_seconds() == 0 {
+    "0".to_string()
+  } else if *dur < Duration::zero() {
+    format!("-{}", format_duration(&-*dur))
+  } else {
+    format!("+{}", format_duration(dur))
+  }
+}
+
+/// Formats duration as "H:MM", e.g. "27:05", empty for zero.
+pub(crate) fn format_hours(dur: &Duration) -> String {
+  if dur.num_minutes() == 0 {
+    return "".to_string();
+  }
+  format!("{}:{:02}", dur.num_hours(), dur.num_minutes() % 60)
+}
+
 fn format_duration_part(p: i64, w: &str) -> String {
   let mut s = format! {"{} {}", p, w};
   if p > 1 {
@@ -87,3 +113,24 @@ fn format_duration_part(p: i64, w: &str) -> String {
   }
   s
 }
+
+/// Formats UTC offset given in seconds, e.g. "UTC+03:00".
+pub(crate) fn format_utc_offset(offset: i32) -> String {
+  let sign = if offset < 0 { '-' } else { '+' };
+  let offset = offset.abs();
+  format!("UTC{}{:02}:{:02}", sign, offset / 3600, offset % 3600 / 60)
+}
+
+/// Formats amount given in hundredths of currency unit.
+pub(crate) fn format_money(amount: usize, currency: &str) -> String {
+  let res = format!("{}.{:02}", amount / 100, amount % 100);
+  if currency.is_empty() {
+    res
+  } else {
+    format!("{} {}", res, currency)
+  }
+}
+
+pub(crate) fn format_rate(rate: &Rate) -> String {
+  format!("{}/h", format_money(rate.amount, &rate.currency))
+}
@@ -3,12 +3,13 @@ extern crate serde_derive;
 #[macro_use]
 extern crate lazy_static;
 
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::core::DBRoot;
 use chrono::prelude::*;
-use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use clap::{App, AppSettings, Arg, ArgGroup, ArgMatches, SubCommand};
 use config::{Config, File};
 use semver::Version;
 
@@ -19,9 +20,15 @@ mod format;
 mod history;
 mod history_storage;
 mod parse;
+mod pick;
 mod print;
 mod report;
+mod schedule;
+mod select;
+mod stale;
 mod style;
+mod timezone;
+mod validate;
 
 #[cfg(feature = "base16")]
 mod base16;
@@ -31,7 +38,8 @@ pub(crate) use format::*;
 use history::DBWatcher;
 pub use print::*;
 pub(crate) use style::*;
-use yatt_orm::sqlite::DB;
+pub(crate) use timezone::AppTz;
+use yatt_orm::sqlite::{Connection, SQLITEResult, DB};
 
 pub struct CrateInfo<'a> {
   pub name: &'a str,
@@ -50,12 +58,77 @@ where
   pub root: PathBuf,
   pub printer: P,
   pub db: &'a T,
+  /// Timezone, dates are parsed and shown in.
+  pub tz: AppTz,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
   pub db_path: String,
   pub history_db_path: String,
+  /// Currency for rates given without one.
+  pub currency: String,
+  /// Hourly rates for tags, e.g. `billable = "100 USD"`.
+  pub tag_rates: HashMap<String, String>,
+  /// Running interval longer than this (e.g. "12h") is
+  /// considered as forgotten to stop.
+  pub max_interval: Option<String>,
+  /// Timezone for parsing and reporting (e.g. "Europe/Berlin"
+  /// or "+03:00"), system one is used by default.
+  pub timezone: Option<String>,
+  /// Month, fiscal year starts from, by name or number
+  /// (e.g. "april" or "4"). Quarters are counted from it too.
+  pub fiscal_year_start: String,
+  /// First day of week, "monday" or "sunday".
+  pub week_start: String,
+  /// Hour, day starts at (e.g. 4 to count work till 04:00
+  /// towards the previous day).
+  pub day_start_hour: u32,
+  pub pomodoro: PomodoroConfig,
+  pub schedule: ScheduleConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct PomodoroConfig {
+  /// Work block length.
+  pub work: String,
+  pub short_break: String,
+  pub long_break: String,
+  /// Long break is taken after this number of work blocks.
+  pub long_break_every: usize,
+}
+impl Default for PomodoroConfig {
+  fn default() -> Self {
+    PomodoroConfig {
+      work: String::from("25m"),
+      short_break: String::from("5m"),
+      long_break: String::from("15m"),
+      long_break_every: 4,
+    }
+  }
+}
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ScheduleConfig {
+  /// Expected work time per weekday, e.g. `mon = "8h"`.
+  /// Days, which aren't listed, are days off.
+  pub hours: HashMap<String, String>,
+  /// Holidays file, either ICS calendar or list of dates
+  /// (YYYY-MM-DD, one per line).
+  pub holidays: Option<String>,
+}
+impl Default for ScheduleConfig {
+  fn default() -> Self {
+    ScheduleConfig {
+      hours: ["mon", "tue", "wed", "thu", "fri"]
+        .iter()
+        .map(|d| (d.to_string(), String::from("8h")))
+        .collect(),
+      holidays: None,
+    }
+  }
 }
 impl Default for AppConfig {
   fn default() -> Self {
@@ -64,10 +137,46 @@ impl Default for AppConfig {
     AppConfig {
       db_path,
       history_db_path,
+      currency: String::new(),
+      tag_rates: HashMap::new(),
+      max_interval: None,
+      timezone: None,
+      fiscal_year_start: String::from("january"),
+      week_start: String::from("monday"),
+      day_start_hour: 0,
+      pomodoro: PomodoroConfig::default(),
+      schedule: ScheduleConfig::default(),
     }
   }
 }
 
+/// Adds column to the table, unless it's already there.
+fn add_column(
+  con: &Connection,
+  table: &str,
+  column: &str,
+  definition: &str,
+) -> SQLITEResult<()> {
+  let mut stmt = con.prepare(&format!(
+    "select name from pragma_table_info('{}')",
+    table
+  ))?;
+  let exists = stmt
+    .query_map([], |r| r.get::<_, String>(0))?
+    .any(|name| name.map(|n| n == column).unwrap_or(false));
+  if !exists {
+    con.execute(
+      &format!(
+        "alter table {} add column {} {}",
+        table, column, definition
+      ),
+      [],
+    )?;
+  }
+
+  Ok(())
+}
+
 fn parse_config(base_path: &Path) -> CliResult<AppConfig> {
   let mut s = Config::new();
   let path = base_path.join("config");
@@ -88,6 +197,12 @@ fn make_args<'a>(info: &CrateInfo<'a>) -> ArgMatches<'a> {
         .help("Unstyled output")
         .short("c"),
     )
+    .arg(
+      Arg::with_name("tz")
+        .long("tz")
+        .help("Timezone name or UTC offset (overrides config)")
+        .takes_value(true),
+    )
     .arg(
       Arg::with_name("theme")
         .help("theme name or inline color list")
@@ -207,6 +322,17 @@ pub fn run(info: CrateInfo) -> CliResult<()> {
       )?;
     }
     let db_sem_ver = Version::parse(&db_ver).unwrap();
+    if db_sem_ver < Version::new(0, 13, 0) {
+      for (table, column, definition) in [
+        ("nodes", "estimate", "INTEGER"),
+        ("nodes", "rate", "INTEGER"),
+        ("nodes", "currency", "TEXT NOT NULL DEFAULT ''"),
+        ("intervals", "pomodoro", "INTEGER NOT NULL DEFAULT 0"),
+        ("intervals", "utc_offset", "INTEGER"),
+      ] {
+        add_column(con, table, column, definition)?;
+      }
+    }
     let crate_ver = clap::crate_version!();
     let crate_sem_ver = Version::parse(crate_ver).unwrap();
 
@@ -218,37 +344,50 @@ pub fn run(info: CrateInfo) -> CliResult<()> {
   })
   .map_err(|e| CliError::Wrapped { source: e.into() })?;
 
+  let args = make_args(&info);
+
+  // Pomodoro session could last for hours, so it's not wrapped
+  // into transaction, and each finished block is saved at once.
+  if args.subcommand_name() == Some("pomodoro") {
+    return run_with_history(&db, base_path, args, conf);
+  }
+
   let db = db.transaction()?;
+  let res = run_with_history(&db, base_path, args, conf);
+  if res.is_ok() {
+    db.commit()?;
+  }
+
+  res
+}
 
+fn run_with_history<T: DBRoot>(
+  db: &T,
+  base_path: PathBuf,
+  args: ArgMatches,
+  conf: AppConfig,
+) -> CliResult<()> {
   let history_db_path = base_path.join(&conf.history_db_path);
-  let res = if history_db_path.exists() {
+  if history_db_path.exists() {
     let hs = {
       match history_storage::sqlite::DB::new(history_db_path) {
         Ok(db) => db,
         Err(e) => return Err(CliError::DB { source: e }),
       }
     };
-    let db = DBWatcher::new(&db, hs);
-    run_app(&db, base_path, &info, conf)
+    let db = DBWatcher::new(db, hs);
+    run_app(&db, base_path, args, conf)
   } else {
-    run_app(&db, base_path, &info, conf)
-  };
-
-  if res.is_ok() {
-    db.commit()?;
+    run_app(db, base_path, args, conf)
   }
-
-  res
 }
 
 fn run_app<T: DBRoot>(
   db: &T,
   base_path: PathBuf,
-  info: &CrateInfo,
+  args: ArgMatches,
   conf: AppConfig,
 ) -> CliResult<()> {
-  let args = make_args(info);
-
   let printer = if args.is_present("no-color") {
     TermPrinter::unstyled()
   } else if let Some(theme) = args.value_of("theme") {
@@ -270,15 +409,24 @@ fn run_app<T: DBRoot>(
     }
   };
 
+  let tz = match timezone(&args, &conf) {
+    Ok(tz) => tz,
+    Err(e) => {
+      print_error(&e, &printer);
+      return Err(e);
+    }
+  };
   let app = AppContext {
     args,
     conf,
     root: base_path,
-    printer,
+    printer: printer.with_timezone(tz),
     db,
+    tz,
   };
 
-  let res = commands::exec(&app);
+  let res =
+    stale::check_running(&app).and_then(|_| commands::exec(&app));
   if res.is_err() {
     print_error(res.as_ref().unwrap_err(), &app.printer);
   }
@@ -286,6 +434,15 @@ fn run_app<T: DBRoot>(
   res
 }
 
+/// Returns timezone given by "tz" argument or config, the system
+/// one is used by default.
+fn timezone(args: &ArgMatches, conf: &AppConfig) -> CliResult<AppTz> {
+  match args.value_of("tz").or(conf.timezone.as_deref()) {
+    Some(tz) => parse::parse_timezone(tz),
+    None => Ok(AppTz::Local),
+  }
+}
+
 fn load_theme(theme: &str, themes_dir: &Path) -> CliResult<Theme> {
   if theme.starts_with("inline:") {
     Theme::try_from(theme.trim_start_matches("inline:"))
@@ -5,12 +5,123 @@ use chrono::Duration;
 use regex::*;
 
 use super::*;
+use crate::core::Rate;
 
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct PeriodOpts {
   pub week_starts_from_sunday: bool,
+  /// Month (1-12), fiscal years and quarters start from.
+  pub fiscal_year_start: u32,
+  /// Hour (0-23), day starts at. Time before it belongs to the
+  /// previous day.
+  pub day_start_hour: u32,
+  pub tz: AppTz,
 }
+impl Default for PeriodOpts {
+  fn default() -> Self {
+    PeriodOpts {
+      week_starts_from_sunday: false,
+      fiscal_year_start: 1,
+      day_start_hour: 0,
+      tz: AppTz::Local,
+    }
+  }
+}
+impl PeriodOpts {
+  pub fn new(conf: &AppConfig, tz: AppTz) -> CliResult<PeriodOpts> {
+    let month = &conf.fiscal_year_start;
+    let fiscal_year_start = month
+      .parse::<u32>()
+      .ok()
+      .filter(|m| (1..=12).contains(m))
+      .or_else(|| {
+        month.parse::<Month>().ok().map(|m| m.number_from_month())
+      })
+      .ok_or_else(|| CliError::Parse {
+        message: format!(
+          "Unknown fiscal year start month \"{}\"",
+          month
+        ),
+      })?;
+
+    let week_starts_from_sunday = match conf.week_start.parse() {
+      Ok(Weekday::Mon) => false,
+      Ok(Weekday::Sun) => true,
+      _ => {
+        return Err(CliError::Parse {
+          message: format!(
+            "Unknown week start \"{}\", use monday or sunday",
+            conf.week_start
+          ),
+        })
+      }
+    };
+    if conf.day_start_hour > 23 {
+      return Err(CliError::Parse {
+        message: format!(
+          "Day start hour should be within 0-23, not {}",
+          conf.day_start_hour
+        ),
+      });
+    }
+
+    Ok(PeriodOpts {
+      week_starts_from_sunday,
+      fiscal_year_start,
+      day_start_hour: conf.day_start_hour,
+      tz,
+    })
+  }
+
+  /// Returns the current day, which lasts till the day start hour
+  /// of the next calendar day.
+  pub fn today(&self) -> Date<AppTz> {
+    (self.now() - self.day_start()).date()
+  }
 
+  pub fn now(&self) -> DateTime<AppTz> {
+    Utc::now().with_timezone(&self.tz)
+  }
+
+  /// Returns the moment, given day starts at.
+  pub fn day_begin(&self, d: Date<AppTz>) -> DateTime<AppTz> {
+    d.and_hms(self.day_start_hour, 0, 0)
+  }
+
+  /// Returns the day, given moment belongs to.
+  pub fn day_of(&self, dt: DateTime<AppTz>) -> Date<AppTz> {
+    (dt - self.day_start()).date()
+  }
+
+  fn day_start(&self) -> Duration {
+    Duration::hours(self.day_start_hour.into())
+  }
+
+  /// Moves date given without time to the day start hour.
+  fn align_date(&self, dt: DateTime<Utc>) -> DateTime<AppTz> {
+    let dt = dt.with_timezone(&self.tz);
+    if dt.date().and_hms(0, 0, 0) == dt {
+      self.day_begin(dt.date())
+    } else {
+      dt
+    }
+  }
+
+  /// Same as `align_date`, but date given without time is
+  /// included, so period ends with the start of the next day.
+  fn align_end_date(&self, dt: DateTime<Utc>) -> DateTime<AppTz> {
+    let dt = dt.with_timezone(&self.tz);
+    if dt.date().and_hms(0, 0, 0) == dt {
+      self.day_begin(dt.date().succ())
+    } else {
+      dt
+    }
+  }
+}
+
+/// Parses period given as "begin::end" dates, single date (till
+/// now), named period or last N units (see `try_parse_period`).
+/// Named periods could be used as range bounds too.
 pub fn parse_period(
   s: &str,
   opts: &PeriodOpts,
@@ -19,20 +130,35 @@ pub fn parse_period(
 
   match parts.len() {
     1 => {
-      let d = try_parse_date_time(parts[0]);
-
-      if let Ok(d) = d {
-        return Ok((d, Utc::now()));
+      if let Some(p) = try_parse_named_period(parts[0], opts) {
+        let (begin, end) = p?;
+        return Ok(cap_period(begin, end));
       }
 
-      try_parse_period(parts[0], opts)
+      match parse_date_time(parts[0], &opts.tz) {
+        Ok(d) => {
+          Ok((opts.align_date(d).with_timezone(&Utc), Utc::now()))
+        }
+        Err(e) => try_parse_period(parts[0], opts).unwrap_or(Err(e)),
+      }
     }
     2 => {
-      let mut end = try_parse_date_time(parts[1])?;
-      if end.date().and_hms(0, 0, 0) == end {
-        end = end + Duration::days(1);
+      let begin = match try_parse_named_period(parts[0], opts) {
+        Some(p) => p?.0,
+        None => opts.align_date(parse_date_time(parts[0], &opts.tz)?),
+      };
+      let end = match try_parse_named_period(parts[1], opts) {
+        Some(p) => p?.1,
+        None => {
+          opts.align_end_date(parse_date_time(parts[1], &opts.tz)?)
+        }
+      };
+      if end < begin {
+        return Err(CliError::Parse {
+          message: format!(r#"period "{}" ends before it begins"#, s),
+        });
       }
-      Ok((try_parse_date_time(parts[0])?, end))
+      Ok(cap_period(begin, end))
     }
     _ => Err(CliError::Parse {
       message: format!(r#"can't parse period from string "{}"#, s),
@@ -40,64 +166,261 @@ pub fn parse_period(
   }
 }
 
+/// Parses duration given as sum of parts with units (e.g. "2h",
+/// "1h30m", "1.5h", "2d4h", "90s") or as HH:MM[:SS]. Units are
+/// d[ays], h[ours], m[in[ute]s] and s[ec[ond]s]. Single unit
+/// without a number means one unit.
 pub fn parse_duration(s: &str) -> CliResult<Duration> {
   lazy_static! {
-    static ref RE_PARSE_DURATION: Regex =
-      Regex::new(r"^(?P<n>\d+)?(?P<p>[dmh])$").unwrap();
+    static ref RE_PARSE_DURATION_CLOCK: Regex =
+      Regex::new(r"^(?P<h>\d+):(?P<m>\d{2})(:(?P<s>\d{2}))?$")
+        .unwrap();
+    static ref RE_PARSE_DURATION_PART: Regex =
+      Regex::new(r"^(?P<n>\d+([.,]\d+)?)?(?P<u>[[:alpha:]]+)")
+        .unwrap();
   }
-  let caps = RE_PARSE_DURATION.captures(s);
-  if caps.is_none() {
+  let err = |part: &str| CliError::Parse {
+    message: if part.is_empty() {
+      format!(r#"can't parse duration from string "{}""#, s)
+    } else {
+      format!(
+        r#"can't parse duration from string "{}" at "{}""#,
+        s, part
+      )
+    },
+  };
+
+  let src: String = s
+    .chars()
+    .filter(|c| !c.is_whitespace())
+    .collect::<String>()
+    .to_lowercase();
+  if let Some(caps) = RE_PARSE_DURATION_CLOCK.captures(&src) {
+    let part = |n| {
+      caps.name(n).map_or(Ok(0), |v| {
+        v.as_str().parse::<i64>().map_err(|_| err(v.as_str()))
+      })
+    };
+    let (h, m, sec) = (part("h")?, part("m")?, part("s")?);
+    if h > 1_000_000 || m > 59 || sec > 59 {
+      return Err(err(&src));
+    }
+    return Ok(
+      Duration::hours(h)
+        + Duration::minutes(m)
+        + Duration::seconds(sec),
+    );
+  }
+
+  let mut rest = src.as_str();
+  let mut res = Duration::zero();
+  let mut seen = Vec::new();
+  while !rest.is_empty() {
+    let caps = RE_PARSE_DURATION_PART
+      .captures(rest)
+      .ok_or_else(|| err(rest))?;
+    let part = &rest[..caps[0].len()];
+    let unit_ms: f64 = match &caps["u"] {
+      "d" | "day" | "days" => 86_400_000.0,
+      "h" | "hr" | "hrs" | "hour" | "hours" => 3_600_000.0,
+      "m" | "min" | "mins" | "minute" | "minutes" => 60_000.0,
+      "s" | "sec" | "secs" | "second" | "seconds" => 1_000.0,
+      _ => return Err(err(part)),
+    };
+    if seen.contains(&(unit_ms as i64)) {
+      return Err(err(part));
+    }
+    seen.push(unit_ms as i64);
+    let n: f64 = match caps.name("n") {
+      Some(n) => n
+        .as_str()
+        .replace(',', ".")
+        .parse()
+        .map_err(|_| err(part))?,
+      None if src.len() == part.len() => 1.0,
+      None => return Err(err(part)),
+    };
+    let ms = (n * unit_ms).round();
+    if ms >= Duration::max_value().num_milliseconds() as f64 {
+      return Err(err(part));
+    }
+    res = res
+      .checked_add(&Duration::milliseconds(ms as i64))
+      .ok_or_else(|| err(part))?;
+    rest = &rest[part.len()..];
+  }
+  if seen.is_empty() {
+    return Err(err(""));
+  }
+
+  Ok(res)
+}
+
+/// Returns moment given by "at" or "ago" argument,
+/// or current time, if none of them present.
+pub fn parse_moment(
+  args: &ArgMatches,
+  tz: &AppTz,
+) -> CliResult<DateTime<Utc>> {
+  let now = Utc::now();
+  let res = if let Some(at) = args.value_of("at") {
+    parse_date_time(at, tz)?
+  } else if let Some(ago) = args.value_of("ago") {
+    now_minus(parse_duration(ago)?)?
+  } else {
+    return Ok(now);
+  };
+
+  if res > now {
     return Err(CliError::Parse {
-      message: format!(r#"can't parse duration from string "{}""#, s),
+      message: "given time is in the future".into(),
     });
   }
-  let caps = caps.unwrap();
-  let n = if let Some(n) = caps.name("n") {
-    n.as_str().parse().map_err(|_| CliError::Parse {
-      message: format!(r#"can't parse duration from string "{}""#, s),
-    })?
+
+  Ok(res)
+}
+
+fn now_minus(d: Duration) -> CliResult<DateTime<Utc>> {
+  Utc::now()
+    .checked_sub_signed(d)
+    .ok_or_else(|| CliError::Parse {
+      message: "given duration is too long".into(),
+    })
+}
+
+/// Parses hourly rate like `120`, `120.50 USD` or `99,9 eur`.
+pub fn parse_rate(s: &str) -> CliResult<Rate> {
+  lazy_static! {
+    static ref RE_PARSE_RATE: Regex = Regex::new(
+      r"^(?P<a>\d+)([.,](?P<f>\d{1,2}))?(\s*(?P<c>[[:alpha:]]+))?$"
+    )
+    .unwrap();
+  }
+  let err = || CliError::Parse {
+    message: format!(r#"can't parse rate from string "{}""#, s),
+  };
+  let caps = RE_PARSE_RATE.captures(s.trim()).ok_or_else(err)?;
+  let units: usize = caps["a"].parse().map_err(|_| err())?;
+  let hundredths: usize = if let Some(f) = caps.name("f") {
+    let f = f.as_str();
+    let v: usize = f.parse().map_err(|_| err())?;
+    if f.len() == 1 {
+      v * 10
+    } else {
+      v
+    }
   } else {
-    1
+    0
   };
-  let p = &caps["p"];
+  let amount = units
+    .checked_mul(100)
+    .and_then(|v| v.checked_add(hundredths))
+    .ok_or_else(err)?;
 
-  match p {
-    "d" => Ok(Duration::days(n)),
-    "h" => Ok(Duration::hours(n)),
-    "m" => Ok(Duration::minutes(n)),
-    _ => unreachable!(),
+  Ok(Rate {
+    amount,
+    currency: caps
+      .name("c")
+      .map(|c| c.as_str().to_uppercase())
+      .unwrap_or_default(),
+  })
+}
+
+/// Parses timezone name (e.g. "Europe/Berlin", "UTC") or UTC
+/// offset (e.g. "+03:00", "UTC-5").
+pub fn parse_timezone(s: &str) -> CliResult<AppTz> {
+  lazy_static! {
+    static ref RE_PARSE_OFFSET: Regex = Regex::new(
+      r"^(?i:utc|gmt)?(?P<s>[+-])(?P<h>\d{1,2})(:?(?P<m>\d{2}))?$"
+    )
+    .unwrap();
+  }
+  let s = s.trim();
+  let err = || CliError::Parse {
+    message: format!(r#"unknown timezone "{}""#, s),
+  };
+
+  if ["utc", "gmt", "z"].contains(&s.to_lowercase().as_str()) {
+    return Ok(AppTz::Fixed(FixedOffset::east_opt(0).unwrap()));
   }
+  if let Some(caps) = RE_PARSE_OFFSET.captures(s) {
+    let h: i32 = caps["h"].parse().map_err(|_| err())?;
+    let m: i32 = caps
+      .name("m")
+      .map_or("0", |m| m.as_str())
+      .parse()
+      .map_err(|_| err())?;
+    if h > 14 || m > 59 {
+      return Err(err());
+    }
+    let secs =
+      (h * 3600 + m * 60) * if &caps["s"] == "-" { -1 } else { 1 };
+    return FixedOffset::east_opt(secs)
+      .map(AppTz::Fixed)
+      .ok_or_else(err);
+  }
+
+  s.parse().map(AppTz::Zone).map_err(|_| err())
 }
 
-fn try_parse_date_time(s: &str) -> CliResult<DateTime<Utc>> {
-  let parts: Vec<&str> = s.split(' ').collect();
-  match parts.len() {
-    1 => {
-      let d = try_parse_date_part(s);
+fn today(tz: &AppTz) -> Date<AppTz> {
+  Utc::now().with_timezone(tz).date()
+}
 
-      if let Ok(d) = d {
-        return Ok(d.and_hms(0, 0, 0).into());
-      }
+/// Parses date and time. Date could be given as YYYY-MM-DD,
+/// DD.MM[.YYYY], "today", "yesterday", [last] weekday name or
+/// month name with day and/or year, optionally followed by time
+/// (HH:MM[:SS]). Moments relative to now are given as "now",
+/// "<duration> ago" or "-<duration>".
+pub fn parse_date_time(
+  s: &str,
+  tz: &AppTz,
+) -> CliResult<DateTime<Utc>> {
+  let s = s.split_whitespace().collect::<Vec<_>>().join(" ");
+  let s = s.to_lowercase();
 
-      try_parse_time_part(s, Local::today())
-    }
-    2 => {
-      let d = try_parse_date_part(parts[0])?;
-      try_parse_time_part(parts[1], d)
+  if s == "now" {
+    return Ok(Utc::now());
+  }
+  if let Some(d) =
+    s.strip_suffix(" ago").or_else(|| s.strip_prefix('-'))
+  {
+    return now_minus(parse_duration(d.trim())?);
+  }
+
+  match try_parse_date_part(&s, tz) {
+    Ok(d) => return Ok(d.and_hms(0, 0, 0).with_timezone(&Utc)),
+    // Time is always given with a colon.
+    Err(e) if !s.contains(':') => return Err(e),
+    Err(_) => {}
+  }
+  match s.rsplit_once(' ') {
+    Some((d, t)) => {
+      try_parse_time_part(t, try_parse_date_part(d, tz)?)
     }
-    _ => Err(CliError::Parse {
-      message: format!(
-        r#"can't parse date and time from string "{}""#,
-        s
-      ),
-    }),
+    None => try_parse_time_part(&s, today(tz)),
   }
 }
 
-fn try_parse_date_part(s: &str) -> CliResult<Date<Local>> {
+fn try_parse_date_part(
+  s: &str,
+  tz: &AppTz,
+) -> CliResult<Date<AppTz>> {
+  match s {
+    "today" => return Ok(today(tz)),
+    "yesterday" => return Ok(today(tz).pred()),
+    _ => {}
+  }
+  if let Some(d) = try_parse_weekday(s, tz) {
+    return Ok(d);
+  }
+  if let Some(d) = try_parse_month_date(s, tz) {
+    return d;
+  }
+
   lazy_static! {
       static ref RE_PARSE_DATE_PART: Regex = Regex::new(
-          r"^((?P<y>\d{4})-)?(?P<m>\d{1,2})-(?P<d>\d{1,2})|(?P<dr>\d{1,2})\.(?P<mr>\d{1,2})(\.(?P<yr>\d{4}))?$"
+          r"^(((?P<y>\d{4})-)?(?P<m>\d{1,2})-(?P<d>\d{1,2})|(?P<dr>\d{1,2})\.(?P<mr>\d{1,2})(\.(?P<yr>\d{4}))?)$"
       )
       .unwrap();
   }
@@ -136,14 +459,80 @@ fn try_parse_date_part(s: &str) -> CliResult<Date<Local>> {
       message: format!(r#"can't parse date from string "{}""#, s),
     })?
   } else {
-    Local::today().year()
+    today(tz).year()
   };
-  Ok(Local.ymd(year, month, day))
+  tz.ymd_opt(year, month, day).single().ok_or_else(|| {
+    CliError::Parse {
+      message: format!(r#"can't parse date from string "{}""#, s),
+    }
+  })
+}
+
+/// Parses "[last] weekday" as the nearest past day with the given
+/// weekday, including today, unless "last" is given.
+fn try_parse_weekday(s: &str, tz: &AppTz) -> Option<Date<AppTz>> {
+  let (last, s) = match s.strip_prefix("last ") {
+    Some(s) => (true, s),
+    None => (false, s),
+  };
+  let weekday: Weekday = s.parse().ok()?;
+  let today = today(tz);
+  let mut days = (7 + today.weekday().num_days_from_monday()
+    - weekday.num_days_from_monday())
+    % 7;
+  if last && days == 0 {
+    days = 7;
+  }
+
+  Some(today - Duration::days(days.into()))
+}
+
+/// Parses dates like "march", "march 2026", "mar 5", "5 march" or
+/// "5 mar 2026". Month without day means it's first day.
+fn try_parse_month_date(
+  s: &str,
+  tz: &AppTz,
+) -> Option<CliResult<Date<AppTz>>> {
+  let parts: Vec<&str> = s.split(' ').collect();
+  let pos = parts.iter().position(|p| p.parse::<Month>().is_ok())?;
+  let month =
+    parts[pos].parse::<Month>().unwrap().number_from_month();
+
+  let err = || CliError::Parse {
+    message: format!(r#"can't parse date from string "{}""#, s),
+  };
+  let mut day = None;
+  let mut year = None;
+  for (i, part) in parts.iter().enumerate() {
+    if i == pos {
+      continue;
+    }
+    if !part.chars().all(|c| c.is_ascii_digit()) {
+      return None;
+    }
+    if part.len() == 4 && i > pos && year.is_none() {
+      year = part.parse::<i32>().ok();
+    } else if part.len() <= 2 && day.is_none() && year.is_none() {
+      day = part.parse::<u32>().ok();
+    } else {
+      return Some(Err(err()));
+    }
+  }
+
+  Some(
+    tz.ymd_opt(
+      year.unwrap_or_else(|| today(tz).year()),
+      month,
+      day.unwrap_or(1),
+    )
+    .single()
+    .ok_or_else(err),
+  )
 }
 
 fn try_parse_time_part(
   s: &str,
-  d: Date<Local>,
+  d: Date<AppTz>,
 ) -> CliResult<DateTime<Utc>> {
   lazy_static! {
     static ref RE_PARSE_TIME_PART: Regex = Regex::new(
@@ -177,31 +566,191 @@ fn try_parse_time_part(
     0
   };
 
-  Ok(d.and_hms(hour, minute, second).into())
+  d.and_hms_opt(hour, minute, second)
+    .map(|d| d.with_timezone(&Utc))
+    .ok_or_else(|| CliError::Parse {
+      message: format!(r#"can't parse time from string "{}""#, s),
+    })
 }
 
-fn try_parse_period(
+/// Period, which is still going, ends now.
+fn cap_period(
+  begin: DateTime<AppTz>,
+  end: DateTime<AppTz>,
+) -> (DateTime<Utc>, DateTime<Utc>) {
+  let now = Utc::now().with_timezone(&begin.timezone());
+  let end = if begin <= now && now < end { now } else { end };
+
+  (begin.with_timezone(&Utc), end.with_timezone(&Utc))
+}
+
+/// Parses calendar aligned periods: "today", "yesterday",
+/// "this-"/"last-" followed by "week", "month", "quarter", "year"
+/// or "fy" (fiscal year), "q"/"pq" (current/previous quarter),
+/// "fy"/"pfy", quarters ("2026-Q3"), fiscal years ("FY2026" starts
+/// in 2026), ISO weeks ("2026-W41"), months ("2026-03", "march",
+/// "march 2026") and years ("2026"). Quarters are counted from the
+/// fiscal year start. Returns whole period bounds.
+fn try_parse_named_period(
   s: &str,
   opts: &PeriodOpts,
-) -> CliResult<(DateTime<Utc>, DateTime<Utc>)> {
-  if s.starts_with('l') && s.len() < 2 {
-    return Err(CliError::Parse {
-      message: format!(r#"can't parse last from string "{}""#, s),
+) -> Option<CliResult<(DateTime<AppTz>, DateTime<AppTz>)>> {
+  let s = s
+    .split(|c: char| c.is_whitespace() || c == '_')
+    .filter(|p| !p.is_empty())
+    .collect::<Vec<_>>()
+    .join("-")
+    .to_lowercase();
+  let err = || CliError::Parse {
+    message: format!(r#"can't parse period from string "{}""#, s),
+  };
+
+  let today = opts.today();
+  let fs = opts.fiscal_year_start;
+  // Months passed since the current fiscal year start.
+  let fm = (today.month() + 12 - fs) % 12;
+  let fy = if today.month() >= fs {
+    today.year()
+  } else {
+    today.year() - 1
+  };
+  let week_start = today
+    - Duration::days(if opts.week_starts_from_sunday {
+      today.weekday().num_days_from_sunday().into()
+    } else {
+      today.weekday().num_days_from_monday().into()
     });
-  }
+  let month =
+    |o: i32| months(&opts.tz, today.year(), today.month(), o, 1);
+  let quarter = |o: i32| {
+    months(
+      &opts.tz,
+      fy,
+      fs,
+      i32::try_from(fm / 3 * 3).unwrap() + 3 * o,
+      3,
+    )
+  };
+  let fiscal_year = |y: i32| months(&opts.tz, y, fs, 0, 12);
+
+  let (begin, end) = match s.as_str() {
+    "today" | "this-day" => (today, today.succ()),
+    "yesterday" | "last-day" => (today.pred(), today),
+    "this-week" => (week_start, week_start + Duration::weeks(1)),
+    "last-week" => (week_start - Duration::weeks(1), week_start),
+    "this-month" => month(0)?,
+    "last-month" => month(-1)?,
+    "q" | "this-quarter" => quarter(0)?,
+    "pq" | "last-quarter" => quarter(-1)?,
+    "this-year" => months(&opts.tz, today.year(), 1, 0, 12)?,
+    "last-year" => months(&opts.tz, today.year() - 1, 1, 0, 12)?,
+    "fy" | "this-fy" => fiscal_year(fy)?,
+    "pfy" | "last-fy" => fiscal_year(fy - 1)?,
+    _ => {
+      lazy_static! {
+        static ref RE_NAMED_PERIOD: Regex = Regex::new(
+          r"^((?P<y>\d{4})(-((q(?P<q>\d))|(w(?P<w>\d{1,2}))|(?P<m>\d{1,2})))?|fy(?P<fy>\d{4}))$"
+        )
+        .unwrap();
+      }
+
+      if let Some(caps) = RE_NAMED_PERIOD.captures(&s) {
+        let num = |n: &str| caps.name(n).map(|v| v.as_str());
+        if let Some(fy) = num("fy") {
+          fiscal_year(fy.parse().ok()?)?
+        } else {
+          let y: i32 = num("y")?.parse().ok()?;
+          if let Some(q) = num("q") {
+            let q: i32 = q.parse().ok()?;
+            if !(1..=4).contains(&q) {
+              return Some(Err(err()));
+            }
+            months(&opts.tz, y, fs, 3 * (q - 1), 3)?
+          } else if let Some(w) = num("w") {
+            let begin = match NaiveDate::from_isoywd_opt(
+              y,
+              w.parse().ok()?,
+              Weekday::Mon,
+            ) {
+              Some(d) => opts.tz.from_local_date(&d).single()?,
+              None => return Some(Err(err())),
+            };
+            (begin, begin + Duration::weeks(1))
+          } else if let Some(m) = num("m") {
+            let m: u32 = m.parse().ok()?;
+            if !(1..=12).contains(&m) {
+              return Some(Err(err()));
+            }
+            months(&opts.tz, y, m, 0, 1)?
+          } else {
+            months(&opts.tz, y, 1, 0, 12)?
+          }
+        }
+      } else {
+        let (m, y) = s.split_once('-').unwrap_or((&s, ""));
+        let m = m.parse::<Month>().ok()?.number_from_month();
+        let y = if y.is_empty() {
+          // The most recent one, including current month.
+          if m > today.month() {
+            today.year() - 1
+          } else {
+            today.year()
+          }
+        } else if y.len() == 4 {
+          y.parse().ok()?
+        } else {
+          return None;
+        };
+        months(&opts.tz, y, m, 0, 1)?
+      }
+    }
+  };
 
+  Some(Ok((opts.day_begin(begin), opts.day_begin(end))))
+}
+
+/// Returns bounds of `len` months, starting `offset` months from
+/// the given one.
+fn months(
+  tz: &AppTz,
+  year: i32,
+  month: u32,
+  offset: i32,
+  len: i32,
+) -> Option<(Date<AppTz>, Date<AppTz>)> {
+  let first = |o: i32| {
+    let m = year * 12 + i32::try_from(month).ok()? - 1 + o;
+    tz.ymd_opt(
+      m.div_euclid(12),
+      u32::try_from(m.rem_euclid(12)).ok()? + 1,
+      1,
+    )
+    .single()
+  };
+
+  Some((first(offset)?, first(offset + len)?))
+}
+
+/// Parses last or previous N units (e.g. "3d", "l2w", "pm"),
+/// `None` is returned, if the string isn't such a period.
+fn try_parse_period(
+  s: &str,
+  opts: &PeriodOpts,
+) -> Option<CliResult<(DateTime<Utc>, DateTime<Utc>)>> {
   lazy_static! {
     static ref RE_PARSE_DURATION: Regex =
       Regex::new(r"^(?P<o>[lp])?(?P<n>\d+)?(?P<p>[ymwdh])$").unwrap();
   }
 
-  let caps = RE_PARSE_DURATION.captures(s);
-  if caps.is_none() {
-    return Err(CliError::Parse {
-      message: format!(r#"can't parse last from string "{}""#, s),
-    });
-  }
-  let caps = caps.unwrap();
+  let caps = RE_PARSE_DURATION.captures(s)?;
+  Some(last_period(s, &caps, opts))
+}
+
+fn last_period(
+  s: &str,
+  caps: &Captures,
+  opts: &PeriodOpts,
+) -> CliResult<(DateTime<Utc>, DateTime<Utc>)> {
   let o: u32 = if let Some(o) = caps.name("o") {
     if o.as_str() == "p" {
       1
@@ -219,10 +768,13 @@ fn try_parse_period(
   } else {
     1
   };
-  let now = Local::now();
+  // Bounds are calculated as if days start at midnight and are
+  // shifted to the day start hour then.
+  let now = opts.now() - opts.day_start();
   let (begin, end) = match p {
     "y" => (
-      Local
+      opts
+        .tz
         .ymd(
           now.year()
             - i32::try_from(o + n - 1).map_err(|_| {
@@ -238,7 +790,7 @@ fn try_parse_period(
         )
         .and_hms(0, 0, 0),
       if o == 1 {
-        Local.ymd(now.year(), 1, 1).and_hms(0, 0, 0)
+        opts.tz.ymd(now.year(), 1, 1).and_hms(0, 0, 0)
       } else {
         now
       },
@@ -263,9 +815,9 @@ fn try_parse_period(
         year -= 1;
       };
       (
-        Local.ymd(year, month, 1).and_hms(0, 0, 0),
+        opts.tz.ymd(year, month, 1).and_hms(0, 0, 0),
         if o == 1 {
-          Local.ymd(now.year(), now.month(), 1).and_hms(0, 0, 0)
+          opts.tz.ymd(now.year(), now.month(), 1).and_hms(0, 0, 0)
         } else {
           now
         },
@@ -297,7 +849,7 @@ fn try_parse_period(
       )
     }
     "d" => {
-      let today = Local::today();
+      let today = now.date();
       let dayo = o + n - 1;
       (
         (today - Duration::days(dayo.try_into().unwrap()))
@@ -306,7 +858,7 @@ fn try_parse_period(
       )
     }
     "h" => {
-      let hour = Local::today().and_hms(now.hour(), 0, 0);
+      let hour = now.date().and_hms(now.hour(), 0, 0);
       let ho = o + n - 1;
       (
         (hour - Duration::hours(ho.try_into().unwrap())),
@@ -316,5 +868,8 @@ fn try_parse_period(
     _ => unreachable!(),
   };
 
-  Ok((begin.into(), end.into()))
+  Ok((
+    (begin + opts.day_start()).with_timezone(&Utc),
+    (end + opts.day_start()).with_timezone(&Utc),
+  ))
 }
@@ -0,0 +1,337 @@
+use std::cmp::Reverse;
+use std::io::{stderr, stdin, Write};
+use std::mem;
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{
+  read, Event, KeyCode, KeyEvent, KeyModifiers,
+};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{
+  self, disable_raw_mode, enable_raw_mode, Clear, ClearType,
+  EnterAlternateScreen, LeaveAlternateScreen,
+};
+use crossterm::tty::IsTty;
+use crossterm::{execute, queue};
+use trees::ForestWalk;
+use yatt_orm::statement::*;
+
+use crate::core::*;
+use crate::*;
+
+/// Kind of entities, which can be picked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PickKind {
+  Task,
+  Group,
+  Interval,
+  Theme,
+}
+
+impl PickKind {
+  pub fn variants() -> &'static [&'static str] {
+    &["task", "group", "interval", "theme"]
+  }
+}
+
+impl std::str::FromStr for PickKind {
+  type Err = CliError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "task" => Ok(PickKind::Task),
+      "group" => Ok(PickKind::Group),
+      "interval" => Ok(PickKind::Interval),
+      "theme" => Ok(PickKind::Theme),
+      _ => Err(CliError::Parse {
+        message: format!("Unknown kind \"{}\"", s),
+      }),
+    }
+  }
+}
+
+struct PickItem {
+  key: String,
+  text: String,
+}
+
+/// Returns value of the argument with given name. If it's
+/// omitted and stdin is a terminal, opens the picker.
+pub(crate) fn value_or_pick<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  args: &ArgMatches,
+  name: &str,
+  kind: PickKind,
+) -> CliResult<String> {
+  if let Some(value) = args.value_of(name) {
+    return Ok(value.to_string());
+  }
+
+  pick_value(ctx, name, kind)
+}
+
+/// Opens the picker for the omitted value with given name, if
+/// stdin is a terminal.
+pub(crate) fn pick_value<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  name: &str,
+  kind: PickKind,
+) -> CliResult<String> {
+  if !stdin().is_tty() {
+    return Err(CliError::Cmd {
+      message: format!("{} is not given", name),
+    });
+  }
+
+  pick(ctx, kind)?.ok_or_else(|| CliError::Cmd {
+    message: "Nothing is selected".into(),
+  })
+}
+
+/// Opens interactive fuzzy picker and returns key of the
+/// selected entity (id or theme name), or None if cancelled.
+pub(crate) fn pick<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+  kind: PickKind,
+) -> CliResult<Option<String>> {
+  let items = match kind {
+    PickKind::Task | PickKind::Group => {
+      task_items(ctx.db, kind == PickKind::Group)?
+    }
+    PickKind::Interval => interval_items(ctx.db, &ctx.tz)?,
+    PickKind::Theme => theme_items(&ctx.root.join("themes"))?,
+  };
+  if items.is_empty() {
+    return Err(CliError::Cmd {
+      message: "There is nothing to pick from".into(),
+    });
+  }
+
+  run_picker(&items).map_err(|e| CliError::wrap(Box::new(e)))
+}
+
+fn task_items<T: DBRoot>(
+  db: &T,
+  groups: bool,
+) -> CliResult<Vec<PickItem>> {
+  let forest = db.get_filtered_forest(eq(Node::deleted_n(), 0))?;
+  let forest = match forest {
+    Some(forest) => forest,
+    None => return Ok(Vec::new()),
+  };
+
+  let mut walk = ForestWalk::from(forest);
+  let mut paths: Vec<Vec<Node>> = if groups {
+    let mut v = FlattenForestIter::new(&mut walk)
+      .filter(|r| r.len() > 1)
+      .map(|mut r| {
+        r.pop();
+        r
+      })
+      .collect::<Vec<_>>();
+    v.sort();
+    v.dedup();
+    v
+  } else {
+    FlattenForestIter::new(&mut walk).collect()
+  };
+  // ForestWalk panics on drop, same as in `list tasks`.
+  mem::forget(walk);
+
+  Ok(
+    paths
+      .drain(..)
+      .map(|p| {
+        let id = p.last().unwrap().id;
+        PickItem {
+          key: id.to_string(),
+          text: format!("[{}] {}", id, format_task_name(&p)),
+        }
+      })
+      .collect(),
+  )
+}
+
+fn interval_items<T: DBRoot>(
+  db: &T,
+  tz: &AppTz,
+) -> CliResult<Vec<PickItem>> {
+  let intervals: Vec<Interval> = db.get_by_statement(
+    filter(eq(Interval::deleted_n(), 0))
+      .sort(Interval::begin_n(), SortDir::Descend),
+  )?;
+
+  let mut res = Vec::new();
+  for interval in intervals {
+    let task = match interval.node_id {
+      Some(id) => format_task_name(&db.ancestors(id)?),
+      None => String::new(),
+    };
+    let end = interval
+      .end
+      .map(|end| format_datetime(&end, tz))
+      .unwrap_or_else(|| "running".into());
+    res.push(PickItem {
+      key: interval.id.to_string(),
+      text: format!(
+        "[{}] {} - {} {}",
+        interval.id,
+        format_datetime(&interval.begin, tz),
+        end,
+        task
+      ),
+    });
+  }
+
+  Ok(res)
+}
+
+fn theme_items(themes_dir: &Path) -> CliResult<Vec<PickItem>> {
+  if !themes_dir.is_dir() {
+    return Ok(Vec::new());
+  }
+
+  let mut res = Vec::new();
+  for entry in fs::read_dir(themes_dir)
+    .map_err(|source| CliError::wrap(Box::new(source)))?
+  {
+    let entry =
+      entry.map_err(|source| CliError::wrap(Box::new(source)))?;
+    if entry.path().is_file() {
+      let name = entry.file_name().to_string_lossy().to_string();
+      res.push(PickItem {
+        key: name.clone(),
+        text: name,
+      });
+    }
+  }
+  res.sort_by(|a, b| a.text.cmp(&b.text));
+
+  Ok(res)
+}
+
+/// Returns score of fuzzy matching of query against text, or None,
+/// if text doesn't contain all query chars in the same order.
+/// Consecutive matches and matches at word starts are scored higher.
+fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+  let text: Vec<char> = text.to_lowercase().chars().collect();
+  let mut score = 0;
+  let mut pos = 0;
+  let mut prev: Option<usize> = None;
+
+  for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace())
+  {
+    let found = text[pos..].iter().position(|c| *c == q)? + pos;
+    score += 1;
+    if prev.map(|p| p + 1) == Some(found) {
+      score += 5;
+    }
+    if found == 0 || !text[found - 1].is_alphanumeric() {
+      score += 3;
+    }
+    prev = Some(found);
+    pos = found + 1;
+  }
+
+  Some(score)
+}
+
+fn run_picker(
+  items: &[PickItem],
+) -> crossterm::Result<Option<String>> {
+  let mut out = stderr();
+  enable_raw_mode()?;
+  execute!(out, EnterAlternateScreen, Hide)?;
+
+  let res = picker_loop(items, &mut out);
+
+  execute!(out, Show, LeaveAlternateScreen)?;
+  disable_raw_mode()?;
+
+  res
+}
+
+fn picker_loop(
+  items: &[PickItem],
+  out: &mut impl Write,
+) -> crossterm::Result<Option<String>> {
+  let mut query = String::new();
+  let mut selected = 0;
+
+  loop {
+    let mut matched: Vec<(i64, &PickItem)> = items
+      .iter()
+      .filter_map(|i| fuzzy_score(&query, &i.text).map(|s| (s, i)))
+      .collect();
+    matched.sort_by_key(|(s, _)| Reverse(*s));
+    selected = selected.min(matched.len().saturating_sub(1));
+
+    let (width, height) = terminal::size()?;
+    let rows = (height as usize).saturating_sub(2);
+    let skip = (selected + 1).saturating_sub(rows);
+
+    queue!(
+      out,
+      MoveTo(0, 0),
+      Clear(ClearType::All),
+      Print(format!("> {}", query)),
+      MoveTo(0, 1),
+      Print(format!("  {}/{}", matched.len(), items.len())),
+    )?;
+    for (n, (_, item)) in
+      matched.iter().enumerate().skip(skip).take(rows)
+    {
+      let text: String = item
+        .text
+        .chars()
+        .take((width as usize).saturating_sub(2))
+        .collect();
+      queue!(out, MoveTo(0, (n - skip + 2) as u16))?;
+      if n == selected {
+        queue!(
+          out,
+          SetAttribute(Attribute::Reverse),
+          Print(format!("> {}", text)),
+          SetAttribute(Attribute::Reset),
+        )?;
+      } else {
+        queue!(out, Print(format!("  {}", text)))?;
+      }
+    }
+    out.flush()?;
+
+    if let Event::Key(KeyEvent { code, modifiers }) = read()? {
+      let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+      match code {
+        KeyCode::Esc => return Ok(None),
+        KeyCode::Char('c') | KeyCode::Char('g') if ctrl => {
+          return Ok(None)
+        }
+        KeyCode::Enter => {
+          return Ok(
+            matched.get(selected).map(|(_, i)| i.key.clone()),
+          )
+        }
+        KeyCode::Up | KeyCode::BackTab => {
+          selected = selected.saturating_sub(1)
+        }
+        KeyCode::Char('p') | KeyCode::Char('k') if ctrl => {
+          selected = selected.saturating_sub(1)
+        }
+        KeyCode::Down | KeyCode::Tab => selected += 1,
+        KeyCode::Char('n') | KeyCode::Char('j') if ctrl => {
+          selected += 1
+        }
+        KeyCode::Char('u') if ctrl => query.clear(),
+        KeyCode::Backspace => {
+          query.pop();
+        }
+        KeyCode::Char(c) => {
+          query.push(c);
+          selected = 0;
+        }
+        _ => {}
+      }
+    }
+  }
+}
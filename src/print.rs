@@ -1,6 +1,7 @@
 use self::core::*;
 use super::*;
 use crate::report::*;
+use std::rc::Rc;
 
 const DEFAULT_INTERVAL_INFO_TITLE: &str = "Interval info:";
 const DEFAULT_TASK_INFO_TITLE: &str = "Task info:";
@@ -63,13 +64,21 @@ pub trait Printer {
   fn interval_cmd(&self, d: &IntervalCmdData);
   fn node_cmd(&self, d: &NodeCmdData);
   fn error(&self, e: &str);
+  fn warning(&self, w: &str);
   fn interval_error(&self, d: &IntervalData, e: &str);
   fn plain(&self, d: &str);
   fn report(&self, r: &Report);
+  /// Prints text as is, with no styling.
+  fn raw(&self, d: &str);
   fn prompt(&self, p: &str);
   fn task_list(&self, tasks: impl Iterator<Item = Vec<Node>>);
   fn interval_list(&self, intervals: impl Iterator<Item = Interval>);
   fn theme_list(&self, list: impl Iterator<Item = ThemeData>);
+  /// Returns printer, which writes to stderr, so messages don't
+  /// mix with command output.
+  fn stderr(&self) -> Self
+  where
+    Self: Sized;
 }
 
 pub trait Markdown {
@@ -78,39 +87,74 @@ pub trait Markdown {
 
 #[derive(Default)]
 pub struct TermPrinter {
-  style: AppStyle,
+  style: Rc<AppStyle>,
+  stderr: bool,
+  tz: AppTz,
+}
+
+/// Prints to stdout or to stderr, depending on the printer.
+macro_rules! out {
+  ($p:expr, $($arg:tt)*) => {
+    if $p.stderr {
+      eprint!($($arg)*)
+    } else {
+      print!($($arg)*)
+    }
+  };
+}
+
+macro_rules! outln {
+  ($p:expr) => {
+    out!($p, "\n")
+  };
+  ($p:expr, $($arg:tt)*) => {{
+    out!($p, $($arg)*);
+    out!($p, "\n")
+  }};
 }
 
 impl Printer for TermPrinter {
   fn interval_cmd(&self, d: &IntervalCmdData) {
-    self.plain(d.cmd_text);
-    println!();
-    print_interval_info(&d.interval, &self.style);
+    if !d.cmd_text.is_empty() {
+      self.plain(d.cmd_text);
+      outln!(self);
+    }
+    print_interval_info(&d.interval, self);
   }
   fn node_cmd(&self, d: &NodeCmdData) {
     if !d.cmd_text.is_empty() {
       self.plain(d.cmd_text);
-      println!();
+      outln!(self);
     }
-    print_node_info(&d.node, &self.style)
+    print_node_info(&d.node, self)
   }
   fn error(&self, e: &str) {
-    println!(
+    outln!(
+      self,
       "{} {}",
       &self.style.plain.apply("Error:"),
       &self.style.error.apply(e)
     );
   }
+  fn warning(&self, w: &str) {
+    outln!(
+      self,
+      "{} {}",
+      &self.style.plain.apply("Warning:"),
+      &self.style.warning.apply(w)
+    );
+  }
   fn interval_error(&self, d: &IntervalData, e: &str) {
     self.error(e);
-    println!();
-    print_interval_info(d, &self.style);
+    outln!(self);
+    print_interval_info(d, self);
   }
   fn plain(&self, d: &str) {
-    println!("{}", &self.style.plain.apply(d));
+    outln!(self, "{}", &self.style.plain.apply(d));
   }
   fn report(&self, r: &Report) {
-    println!(
+    outln!(
+      self,
       "{}",
       self
         .style
@@ -118,39 +162,61 @@ impl Printer for TermPrinter {
         .text(&r.markdown(), self.style.screen_width)
     );
   }
+  fn raw(&self, d: &str) {
+    outln!(self, "{}", d);
+  }
   fn prompt(&self, p: &str) {
-    println!("{}", p);
+    outln!(self, "{}", p);
   }
   fn task_list(&self, tasks: impl Iterator<Item = Vec<Node>>) {
-    print_task_list(tasks, &self.style);
+    print_task_list(tasks, self);
   }
   fn interval_list(&self, intervals: impl Iterator<Item = Interval>) {
-    print_intervals_list(intervals, &self.style);
+    print_intervals_list(intervals, self);
   }
   fn theme_list(&self, list: impl Iterator<Item = ThemeData>) {
-    print_theme_list(list);
+    print_theme_list(list, self);
+  }
+  fn stderr(&self) -> Self {
+    TermPrinter {
+      style: self.style.clone(),
+      stderr: true,
+      tz: self.tz,
+    }
   }
 }
 
 impl TermPrinter {
+  pub(crate) fn with_timezone(self, tz: AppTz) -> Self {
+    TermPrinter { tz, ..self }
+  }
   pub(crate) fn unstyled() -> Self {
     TermPrinter {
-      style: AppStyle::empty(),
+      style: Rc::new(AppStyle::empty()),
+      stderr: false,
+      tz: AppTz::Local,
     }
   }
   pub(crate) fn new(colors: &Theme) -> Self {
     TermPrinter {
-      style: AppStyle::new(colors),
+      style: Rc::new(AppStyle::new(colors)),
+      stderr: false,
+      tz: AppTz::Local,
     }
   }
 }
 
-fn print_task_list(d: impl Iterator<Item = Vec<Node>>, s: &AppStyle) {
+fn print_task_list(
+  d: impl Iterator<Item = Vec<Node>>,
+  p: &TermPrinter,
+) {
+  let s = &p.style;
   let plain = &s.plain;
   let s = &s.task_list;
   for task in d {
     let last = task.last().unwrap();
-    print!(
+    out!(
+      p,
       "{}{}{} ",
       plain.apply('['),
       s.id.apply(last.id),
@@ -158,30 +224,36 @@ fn print_task_list(d: impl Iterator<Item = Vec<Node>>, s: &AppStyle) {
     );
     for (i, t) in task.iter().enumerate() {
       if i > 0 {
-        print!(" {} ", plain.apply('>'));
+        out!(p, " {} ", plain.apply('>'));
       }
-      print!("{}", s.name.apply(&t.label));
+      out!(p, "{}", s.name.apply(&t.label));
     }
-    println!(" {} ", plain.apply(format_datetime(&last.created)));
+    outln!(
+      p,
+      " {} ",
+      plain.apply(format_datetime(&last.created, &p.tz))
+    );
   }
 }
 
 fn print_intervals_list(
   d: impl Iterator<Item = Interval>,
-  s: &AppStyle,
+  p: &TermPrinter,
 ) {
+  let s = &p.style;
   let plain = &s.plain;
   let s = &s.task_list;
   for i in d {
     if i.end.is_some() {
-      println!(
+      outln!(
+        p,
         "{}{}{} {} {} {} {} {}",
         plain.apply('['),
         s.id.apply(i.id),
         plain.apply(']'),
-        s.name.apply(format_datetime(&i.begin)),
+        s.name.apply(format_datetime(&i.begin, &p.tz)),
         plain.apply('-'),
-        s.name.apply(format_datetime(&i.end.unwrap())),
+        s.name.apply(format_datetime(&i.end.unwrap(), &p.tz)),
         plain.apply("task id:"),
         s.name.apply(i.node_id.unwrap()),
       );
@@ -189,28 +261,32 @@ fn print_intervals_list(
   }
 }
 
-fn print_interval_info(d: &IntervalData, s: &AppStyle) {
+fn print_interval_info(d: &IntervalData, p: &TermPrinter) {
+  let s = &p.style;
   let plain = &s.plain;
   let s = &s.task;
-  println!("{}", plain.apply(d.title));
-  print!("  {} ", plain.apply("Task:"));
+  outln!(p, "{}", plain.apply(d.title));
+  out!(p, "  {} ", plain.apply("Task:"));
   for (i, t) in d.task.iter().enumerate() {
-    print!("{}", s.name.apply(&t.label));
+    out!(p, "{}", s.name.apply(&t.label));
     if i < d.task.len() - 1 {
-      print!(" {} ", plain.apply('>'));
+      out!(p, " {} ", plain.apply('>'));
     }
   }
-  println!();
-  print!(
+  outln!(p);
+  out!(
+    p,
     "  {} {}",
     plain.apply("Started:"),
-    s.start_time.apply(format_datetime(&d.interval.begin))
+    s.start_time
+      .apply(format_datetime(&d.interval.begin, &p.tz))
   );
 
   let dur = Utc::now() - d.interval.begin;
 
   if dur.num_seconds() > 2 {
-    print!(
+    out!(
+      p,
       " {}{} {}{}",
       plain.apply('('),
       s.time_span.apply(format_duration(&dur)),
@@ -221,14 +297,16 @@ fn print_interval_info(d: &IntervalData, s: &AppStyle) {
 
   if d.interval.end.is_some() {
     let e = d.interval.end.unwrap();
-    print!(
+    out!(
+      p,
       "\n  {} {}",
       plain.apply("Stopped:"),
-      s.end_time.apply(format_datetime(&e))
+      s.end_time.apply(format_datetime(&e, &p.tz))
     );
     let dur = Utc::now() - e;
     if dur.num_seconds() > 2 {
-      print!(
+      out!(
+        p,
         " {}{} {}{}",
         plain.apply('('),
         s.time_span.apply(format_duration(&dur)),
@@ -238,32 +316,64 @@ fn print_interval_info(d: &IntervalData, s: &AppStyle) {
     }
   }
 
-  println!();
+  // Interval recorded in other timezone is shown with it's offset.
+  if let Some(offset) = d.interval.utc_offset {
+    let begin = d.interval.begin.with_timezone(&p.tz);
+    if begin.offset().fix().local_minus_utc() != offset {
+      out!(
+        p,
+        "\n  {} {}",
+        plain.apply("Recorded in:"),
+        plain.apply(format_utc_offset(offset))
+      );
+    }
+  }
+
+  outln!(p);
 }
 
-fn print_node_info(d: &NodeData, s: &AppStyle) {
+fn print_node_info(d: &NodeData, p: &TermPrinter) {
+  let s = &p.style;
   let plain = &s.plain;
   let s = &s.task;
   if !d.title.is_empty() {
-    println!("{}", plain.apply(d.title));
+    outln!(p, "{}", plain.apply(d.title));
   }
-  print!("  {} ", plain.apply("Task:"));
+  out!(p, "  {} ", plain.apply("Task:"));
   for (i, t) in d.node.iter().enumerate() {
-    print!("{}", s.name.apply(&t.label));
+    out!(p, "{}", s.name.apply(&t.label));
     if i < d.node.len() - 1 {
-      print!(" {} ", plain.apply('>'));
+      out!(p, " {} ", plain.apply('>'));
     }
   }
-  println!();
+  outln!(p);
   let last = d.node.last().unwrap();
-  print!(
+  out!(
+    p,
     "  {} {}",
     plain.apply("Created:"),
-    s.created_time.apply(format_datetime(&last.created))
+    s.created_time.apply(format_datetime(&last.created, &p.tz))
   );
-  println!();
+  outln!(p);
+  if let Some(estimate) = last.get_estimate() {
+    outln!(
+      p,
+      "  {} {}",
+      plain.apply("Estimate:"),
+      s.time_span.apply(format_duration(&estimate))
+    );
+  }
+  if let Some(rate) = last.get_rate() {
+    outln!(
+      p,
+      "  {} {}",
+      plain.apply("Rate:"),
+      s.time_span.apply(format_rate(&rate))
+    );
+  }
   if !last.tags.is_empty() {
-    print!(
+    out!(
+      p,
       "  {} {}",
       plain.apply("Tags:"),
       s.tags.apply(
@@ -276,10 +386,13 @@ fn print_node_info(d: &NodeData, s: &AppStyle) {
       )
     );
   }
-  println!();
+  outln!(p);
 }
 
-fn print_theme_list(list: impl Iterator<Item = ThemeData>) {
+fn print_theme_list(
+  list: impl Iterator<Item = ThemeData>,
+  p: &TermPrinter,
+) {
   let mut print_list: Vec<ThemeData> = list.collect();
   if print_list.is_empty() {
     return;
@@ -293,7 +406,8 @@ fn print_theme_list(list: impl Iterator<Item = ThemeData>) {
     let t = item.theme;
     let pad = " ".repeat(title_max_len - item.title.len());
 
-    println!(
+    outln!(
+      p,
       "   {}: {}{}{}{}{}{}",
       item.title,
       pad,
@@ -1,16 +1,22 @@
 use crate::format::*;
 use crate::print::Markdown;
+use crate::AppTz;
 use chrono::prelude::*;
 use chrono::Duration;
+use serde_json::{json, Value};
 
 #[derive(Default)]
 pub struct Report {
   rows: Vec<Row>,
+  nested_column: Option<String>,
 }
 
 impl Report {
   pub fn new() -> Self {
-    Report { rows: vec![] }
+    Report {
+      rows: vec![],
+      nested_column: None,
+    }
   }
   pub fn push(&mut self, r: impl Into<Row>) {
     self.rows.push(r.into());
@@ -18,11 +24,197 @@ impl Report {
   pub fn rows(&self) -> &Vec<Row> {
     &self.rows
   }
+
+  /// Sets name of the CSV column, labels of nested rows are put
+  /// in ("Subtask" by default).
+  pub fn set_nested_column(&mut self, name: &str) {
+    self.nested_column = Some(name.to_string());
+  }
+
+  /// Renders the first table of report as CSV, header record
+  /// first. Title and period are left out. Nested rows keep their
+  /// parent label in the first column, their own one goes to the
+  /// column next to it. Durations are given as H:MM:SS.
+  pub fn csv(&self) -> String {
+    let mut rows = self
+      .rows
+      .iter()
+      .skip_while(|r| !matches!(r, Row::TableHeader(_)))
+      .take_while(|r| !matches!(r, Row::Header(_)));
+    let mut header = match rows.next() {
+      Some(Row::TableHeader(v)) => v.to_owned(),
+      _ => return String::new(),
+    };
+    let rows: Vec<&Row> = rows.collect();
+    let nested = rows.iter().any(|r| matches!(r, Row::Nested(_)));
+    if nested {
+      header.insert(
+        1,
+        self
+          .nested_column
+          .to_owned()
+          .unwrap_or_else(|| "Subtask".to_string()),
+      );
+    }
+
+    let cells = |v: &[Cell]| v.iter().map(Cell::csv).collect();
+    let mut res = vec![csv_record(&header)];
+    // Labels of the current row and its parents, by depth.
+    let mut path: Vec<String> = Vec::new();
+    for row in rows {
+      let mut fields: Vec<String> = match row {
+        Row::Table(v) => {
+          path = v.iter().take(1).map(Cell::csv).collect();
+          cells(v)
+        }
+        Row::Nested(v) => {
+          let depth = v
+            .iter()
+            .find_map(|c| match c {
+              Cell::Nested(_, p) => Some(*p),
+              _ => None,
+            })
+            .unwrap_or(1);
+          path.truncate(depth);
+          path.extend(v.iter().take(1).map(Cell::csv));
+          std::iter::once(path[0].to_owned())
+            .chain(std::iter::once(path[1..].join(" -> ")))
+            .chain(v.iter().skip(1).map(Cell::csv))
+            .collect()
+        }
+        Row::SubTotal(v) => {
+          std::iter::once(String::new()).chain(cells(v)).collect()
+        }
+        Row::Total(v) => std::iter::once("Total".to_string())
+          .chain(cells(v))
+          .collect(),
+        _ => continue,
+      };
+      if nested && !matches!(row, Row::Nested(_)) {
+        fields.insert(1.min(fields.len()), String::new());
+      }
+      fields.resize(header.len(), String::new());
+      res.push(csv_record(&fields));
+    }
+
+    res.join("\n")
+  }
+
+  /// Renders report as JSON array of typed rows. Durations are
+  /// given in seconds, money amounts as decimal strings.
+  pub fn json(&self) -> String {
+    let cells =
+      |v: &[Cell]| -> Value { v.iter().map(Cell::json).collect() };
+    let rows: Vec<Value> = self
+      .rows
+      .iter()
+      .filter_map(|row| {
+        Some(match row {
+          Row::Header(v) => json!({"type": "header", "title": v}),
+          Row::Interval(b, e) => json!({
+            "type": "period",
+            "begin": format_rfc3339(b),
+            "end": format_rfc3339(e),
+          }),
+          Row::TableHeader(v) => {
+            json!({"type": "columns", "names": v})
+          }
+          Row::Table(v) => json!({"type": "row", "cells": cells(v)}),
+          Row::Nested(v) => json!({
+            "type": "nested",
+            "depth": v.iter().find_map(|c| match c {
+              Cell::Nested(_, p) => Some(*p),
+              _ => None,
+            }),
+            "cells": cells(v),
+          }),
+          Row::SubTotal(v) => {
+            json!({"type": "subtotal", "cells": cells(v)})
+          }
+          Row::Total(v) => {
+            json!({"type": "total", "cells": cells(v)})
+          }
+          Row::Span => return None,
+        })
+      })
+      .collect();
+
+    serde_json::to_string_pretty(&rows).unwrap()
+  }
+
+  /// Renders report as standalone HTML page.
+  pub fn html(&self) -> String {
+    let title = self
+      .rows
+      .iter()
+      .find_map(|r| match r {
+        Row::Header(v) => Some(v.as_str()),
+        _ => None,
+      })
+      .unwrap_or("Report");
+    let mut res = format!(
+      "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+      <title>{}</title>\n</head>\n<body>\n",
+      html_escape(title)
+    );
+    let mut in_table = false;
+    for row in self.rows.iter() {
+      let is_table =
+        !matches!(row, Row::Header(_) | Row::Interval(_, _));
+      if is_table && !in_table {
+        res += "<table>\n";
+      } else if !is_table && in_table {
+        res += "</table>\n";
+      }
+      in_table = is_table;
+
+      res += &match row {
+        Row::Header(v) => format!("<h1>{}</h1>", html_escape(v)),
+        Row::Interval(b, e) => {
+          let dtopts = DateTimeOpts {
+            always_long: true,
+            no_string_now: true,
+          };
+          format!(
+            "<p>Period: {} - {}</p>",
+            format_datetime_opts(b, &dtopts),
+            format_datetime_opts(e, &dtopts)
+          )
+        }
+        Row::TableHeader(v) => format!(
+          "<tr>{}</tr>",
+          v.iter()
+            .map(|c| format!("<th>{}</th>", html_escape(c)))
+            .collect::<String>()
+        ),
+        Row::Table(v) => format!("<tr>{}</tr>", html_cells(v, "td")),
+        Row::Nested(v) => {
+          format!("<tr class=\"nested\">{}</tr>", html_cells(v, "td"))
+        }
+        Row::SubTotal(v) => format!(
+          "<tr class=\"subtotal\"><td></td>{}</tr>",
+          html_cells(v, "td")
+        ),
+        Row::Total(v) => format!(
+          "<tr class=\"total\"><th>Total</th>{}</tr>",
+          html_cells(v, "th")
+        ),
+        Row::Span => "<tr><td></td></tr>".to_string(),
+      };
+      res += "\n";
+    }
+    if in_table {
+      res += "</table>\n";
+    }
+    res += "</body>\n</html>";
+
+    res
+  }
 }
 
 pub enum Row {
   Header(String),
-  Interval(DateTime<Utc>, DateTime<Utc>),
+  Interval(DateTime<AppTz>, DateTime<AppTz>),
   Table(Vec<Cell>),
   TableHeader(Vec<String>),
   SubTotal(Vec<Cell>),
@@ -41,8 +233,8 @@ impl From<&str> for Row {
     Row::Header(v.into())
   }
 }
-impl From<(DateTime<Utc>, DateTime<Utc>)> for Row {
-  fn from(v: (DateTime<Utc>, DateTime<Utc>)) -> Self {
+impl From<(DateTime<AppTz>, DateTime<AppTz>)> for Row {
+  fn from(v: (DateTime<AppTz>, DateTime<AppTz>)) -> Self {
     Row::Interval(v.0, v.1)
   }
 }
@@ -61,8 +253,17 @@ pub enum Cell {
   Usize(usize),
   Isize(isize),
   String(String),
-  DateTime(DateTime<Utc>),
+  DateTime(DateTime<AppTz>),
   Duration(Duration),
+  /// Duration shown with a sign, e.g. difference from expected.
+  SignedDuration(Duration),
+  /// Duration in compact "H:MM" form, suitable for grids.
+  Hours(Duration),
+  /// Share in percents, shown with a "%" sign.
+  Percent(usize),
+  Money(usize, String),
+  /// Text followed by a warning mark.
+  Warning(String),
   Nested(Box<Cell>, usize),
   Span,
 }
@@ -87,8 +288,8 @@ impl From<&str> for Cell {
     Cell::String(v.into())
   }
 }
-impl From<DateTime<Utc>> for Cell {
-  fn from(v: DateTime<Utc>) -> Self {
+impl From<DateTime<AppTz>> for Cell {
+  fn from(v: DateTime<AppTz>) -> Self {
     Cell::DateTime(v)
   }
 }
@@ -143,7 +344,10 @@ fn format_subtotal(cells: &[Cell]) -> String {
   let mut cols = "|".to_string();
   for c in cells {
     aligns += "|";
-    cols += &format!("|*{}*", c.markdown());
+    cols += &match c {
+      Cell::Span => "|".to_string(),
+      _ => format!("|*{}*", c.markdown()),
+    };
   }
   format!("{}\n{}", aligns, cols)
 }
@@ -155,13 +359,19 @@ fn format_total(cells: &[Cell]) -> String {
     aligns += match c {
       Cell::String(_)
       | Cell::Duration(_)
+      | Cell::SignedDuration(_)
+      | Cell::Warning(_)
       | Cell::DateTime(_)
-      | Cell::Nested(_, _) => "|-",
+      | Cell::Nested(_, _)
+      | Cell::Span => "|-",
       _ => "|-:",
     };
-    cols += &format!("|**{}**\n|-", c.markdown());
+    cols += &match c {
+      Cell::Span => "|".to_string(),
+      _ => format!("|**{}**", c.markdown()),
+    };
   }
-  format!("{}\n{}", aligns, cols)
+  format!("{}\n{}\n|-", aligns, cols)
 }
 
 fn format_header(cells: &[String]) -> String {
@@ -181,6 +391,8 @@ fn format_cells(cells: &[Cell]) -> String {
     aligns += match c {
       Cell::String(_)
       | Cell::Duration(_)
+      | Cell::SignedDuration(_)
+      | Cell::Warning(_)
       | Cell::DateTime(_)
       | Cell::Nested(_, _) => "|-",
       _ => "|-:",
@@ -190,14 +402,116 @@ fn format_cells(cells: &[Cell]) -> String {
   format!("{}\n{}", aligns, cols)
 }
 
+impl Cell {
+  fn csv(&self) -> String {
+    match self {
+      Cell::DateTime(v) => format_rfc3339(v),
+      Cell::Duration(v)
+      | Cell::SignedDuration(v)
+      | Cell::Hours(v) => format_hms(v),
+      Cell::Percent(v) => v.to_string(),
+      Cell::Nested(v, _) => v.csv(),
+      _ => self.text(),
+    }
+  }
+
+  fn json(&self) -> Value {
+    match self {
+      Cell::Usize(v) => json!(v),
+      Cell::Isize(v) => json!(v),
+      Cell::String(v) => json!(v),
+      Cell::DateTime(v) => json!(format_rfc3339(v)),
+      Cell::Duration(v)
+      | Cell::SignedDuration(v)
+      | Cell::Hours(v) => json!(v.num_seconds()),
+      Cell::Percent(v) => json!(v),
+      Cell::Money(v, c) => {
+        json!({"amount": format_money(*v, ""), "currency": c})
+      }
+      Cell::Warning(_) => json!(self.text()),
+      Cell::Nested(v, _) => v.json(),
+      Cell::Span => Value::Null,
+    }
+  }
+
+  /// Plain text of the cell, as it's shown to user.
+  fn text(&self) -> String {
+    match self {
+      Cell::Nested(v, _) => v.text(),
+      Cell::Warning(v) => format!("{} !", v),
+      _ => self.markdown(),
+    }
+  }
+}
+
+fn html_cells(cells: &[Cell], tag: &str) -> String {
+  cells
+    .iter()
+    .map(|c| match c {
+      Cell::Nested(v, p) => format!(
+        "<{0} style=\"padding-left: {1}em\">{2}</{0}>",
+        tag,
+        p * 2,
+        html_escape(&v.text())
+      ),
+      _ => format!("<{0}>{1}</{0}>", tag, html_escape(&c.text())),
+    })
+    .collect()
+}
+
+fn html_escape(s: &str) -> String {
+  s.replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+fn csv_record(fields: &[String]) -> String {
+  fields
+    .iter()
+    .map(|f| csv_field(f))
+    .collect::<Vec<String>>()
+    .join(",")
+}
+
+fn csv_field(s: &str) -> String {
+  if s.contains([',', '"', '\n', '\r']) || s.trim() != s {
+    format!("\"{}\"", s.replace('"', "\"\""))
+  } else {
+    s.to_string()
+  }
+}
+
+fn format_rfc3339(dt: &DateTime<AppTz>) -> String {
+  dt.to_rfc3339_opts(SecondsFormat::Secs, false)
+}
+
+fn format_hms(dur: &Duration) -> String {
+  let secs = dur.num_seconds().abs();
+  format!(
+    "{}{}:{:02}:{:02}",
+    if dur.num_seconds() < 0 { "-" } else { "" },
+    secs / 3600,
+    secs % 3600 / 60,
+    secs % 60
+  )
+}
+
 impl Markdown for Cell {
   fn markdown(&self) -> String {
     match self {
       Cell::Usize(v) => v.to_string(),
       Cell::Isize(v) => v.to_string(),
       Cell::String(v) => v.to_owned(),
-      Cell::DateTime(v) => format_datetime(v),
+      Cell::DateTime(v) => {
+        format_datetime_opts(v, &DateTimeOpts::default())
+      }
       Cell::Duration(v) => format_duration(v),
+      Cell::SignedDuration(v) => format_duration_signed(v),
+      Cell::Hours(v) => format_hours(v),
+      Cell::Percent(v) => format!("{}%", v),
+      Cell::Money(v, c) => format_money(*v, c),
+      Cell::Warning(v) => format!("{} **!**", v),
       Cell::Nested(v, p) => {
         let mut pad = "".to_string();
         let mark = match p {
@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+
+use chrono::prelude::*;
+use chrono::Duration;
+
+use crate::parse::parse_duration;
+use crate::*;
+
+/// Expected work hours per weekday along with holidays.
+pub(crate) struct Schedule {
+  hours: [Duration; 7],
+  holidays: HashSet<NaiveDate>,
+}
+
+impl Schedule {
+  /// Builds schedule from config, holidays file path is resolved
+  /// against application directory, if it is relative.
+  pub fn new(
+    conf: &ScheduleConfig,
+    root: &Path,
+  ) -> CliResult<Schedule> {
+    let mut hours = [Duration::zero(); 7];
+    for (day, value) in conf.hours.iter() {
+      let day: Weekday =
+        day.parse().map_err(|_| CliError::Parse {
+          message: format!("Unknown weekday \"{}\" in schedule", day),
+        })?;
+      hours[day.num_days_from_monday() as usize] =
+        parse_duration(value)?;
+    }
+
+    let holidays = match &conf.holidays {
+      Some(path) => {
+        let path = root.join(path);
+        let text = fs::read_to_string(&path)
+          .map_err(|source| CliError::Io { source })?;
+        let is_ics =
+          path.extension().map(|e| e.eq_ignore_ascii_case("ics"))
+            == Some(true)
+            || text.trim_start().starts_with("BEGIN:VCALENDAR");
+        if is_ics {
+          parse_ics(&text)?
+        } else {
+          parse_list(&text)?
+        }
+      }
+      None => HashSet::new(),
+    };
+
+    Ok(Schedule { hours, holidays })
+  }
+
+  pub fn is_holiday(&self, date: &NaiveDate) -> bool {
+    self.holidays.contains(date)
+  }
+
+  /// Returns expected work time for the given day.
+  pub fn expected(&self, date: &NaiveDate) -> Duration {
+    if self.is_holiday(date) {
+      return Duration::zero();
+    }
+    self.hours[date.weekday().num_days_from_monday() as usize]
+  }
+}
+
+/// Parses list of dates, one per line in YYYY-MM-DD format,
+/// optionally followed by description. Empty lines and lines
+/// starting with "#" are skipped.
+fn parse_list(text: &str) -> CliResult<HashSet<NaiveDate>> {
+  let mut res = HashSet::new();
+  for (n, line) in text.lines().enumerate() {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+    let date = line.split_whitespace().next().unwrap();
+    let date =
+      NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+        CliError::Parse {
+          message: format!(
+            "Unable to parse holiday date \"{}\" at line {}",
+            date,
+            n + 1
+          ),
+        }
+      })?;
+    res.insert(date);
+  }
+
+  Ok(res)
+}
+
+/// Collects days of all events from ICS calendar. End date of
+/// the event is exclusive, recurrence rules are not supported.
+fn parse_ics(text: &str) -> CliResult<HashSet<NaiveDate>> {
+  // Long lines are folded, continuation starts with whitespace.
+  let mut lines: Vec<String> = Vec::new();
+  for line in text.lines() {
+    if line.starts_with(' ') || line.starts_with('\t') {
+      if let Some(last) = lines.last_mut() {
+        last.push_str(&line[1..]);
+      }
+    } else {
+      lines.push(line.trim_end().to_string());
+    }
+  }
+
+  let mut res = HashSet::new();
+  let mut start: Option<NaiveDate> = None;
+  let mut end: Option<NaiveDate> = None;
+  for line in lines {
+    let (name, value) = match line.split_once(':') {
+      Some(v) => v,
+      None => continue,
+    };
+    let name = name.split(';').next().unwrap().to_uppercase();
+    match name.as_str() {
+      "BEGIN" if value.eq_ignore_ascii_case("VEVENT") => {
+        start = None;
+        end = None;
+      }
+      "DTSTART" => start = Some(parse_ics_date(value)?),
+      "DTEND" => end = Some(parse_ics_date(value)?),
+      "END" if value.eq_ignore_ascii_case("VEVENT") => {
+        let start = match start {
+          Some(start) => start,
+          None => continue,
+        };
+        res.insert(start);
+        if let Some(end) = end {
+          let mut day = start.succ();
+          while day < end {
+            res.insert(day);
+            day = day.succ();
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+
+  Ok(res)
+}
+
+fn parse_ics_date(value: &str) -> CliResult<NaiveDate> {
+  value
+    .get(..8)
+    .and_then(|v| NaiveDate::parse_from_str(v, "%Y%m%d").ok())
+    .ok_or_else(|| CliError::Parse {
+      message: format!("Unable to parse calendar date \"{}\"", value),
+    })
+}
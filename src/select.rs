@@ -0,0 +1,254 @@
+use std::convert::TryInto;
+
+use crate::core::*;
+use crate::errors::*;
+use crate::format::format_task_name;
+use yatt_orm::{statement::*, DBError, FieldVal};
+
+/// Returns interval by it's id, or by negative offset from now
+/// (-1 is the last finished interval).
+pub(crate) fn select_interval<T: DBRoot>(
+  db: &T,
+  s: &str,
+) -> CliResult<Interval> {
+  let id: i64 = s.trim().parse().map_err(|_| CliError::Parse {
+    message: "Unable to parse interval ID".into(),
+  })?;
+
+  let out_of_range = || CliError::Parse {
+    message: "Interval ID is out of range".into(),
+  };
+  if id < 0 {
+    let offset: usize = id
+      .checked_neg()
+      .and_then(|v| v.try_into().ok())
+      .ok_or_else(out_of_range)?;
+    let intervals: Vec<Interval> = db.get_by_statement(
+      filter(and(
+        ne(Interval::deleted_n(), 1),
+        ne(Interval::end_n(), FieldVal::Null),
+      ))
+      .sort(Interval::begin_n(), SortDir::Descend)
+      .limit(offset),
+    )?;
+    if intervals.len() < offset {
+      return Err(CliError::Cmd {
+        message: "There is no interval with given offset".into(),
+      });
+    }
+    Ok(intervals.last().unwrap().to_owned())
+  } else {
+    let id: usize = id.try_into().map_err(|_| out_of_range())?;
+    let interval =
+      db.get_by_id::<Interval>(id).map_err(|source| {
+        if let DBError::IsEmpty { .. } = source {
+          return CliError::Cmd {
+            message: "There is no interval with given ID".into(),
+          };
+        }
+
+        CliError::DB { source }
+      })?;
+    if interval.deleted {
+      return Err(CliError::Cmd {
+        message: "Interval is deleted".into(),
+      });
+    }
+    Ok(interval)
+  }
+}
+
+/// Returns intervals by selector, which is either a single interval
+/// selector (see [select_interval]), or a range "A..B" of them. Range
+/// includes all intervals started between A and B, sorted by begin.
+pub(crate) fn select_intervals<T: DBRoot>(
+  db: &T,
+  s: &str,
+) -> CliResult<Vec<Interval>> {
+  let (first, last) = match s.split_once("..") {
+    Some(range) => range,
+    None => return Ok(vec![select_interval(db, s)?]),
+  };
+  let mut first = select_interval(db, first)?;
+  let mut last = select_interval(db, last)?;
+  if first.begin > last.begin {
+    std::mem::swap(&mut first, &mut last);
+  }
+
+  Ok(
+    db.get_by_statement(
+      filter(and(
+        and(
+          ne(Interval::deleted_n(), 1),
+          not(lt(Interval::begin_n(), first.begin)),
+        ),
+        not(gt(Interval::begin_n(), last.begin)),
+      ))
+      .sort(Interval::begin_n(), SortDir::Ascend),
+    )?,
+  )
+}
+
+/// Help text for arguments, which are resolved by [select_task].
+pub(crate) const TASK_SELECTOR_HELP: &str =
+  "Task id, \"::\" delimited path, \
+  \"cur\" (running task), \"last\" or -N (N-th most recent task) \
+  or unique part of task name";
+
+/// Help text for arguments, which are resolved by [select_tasks].
+pub(crate) const TASKS_SELECTOR_HELP: &str = "Comma separated task \
+  selectors: ids, \"::\" delimited paths, \"cur\", \"last\", -N \
+  or unique parts of task names";
+
+/// Returns task by selector, which could be a task id, a path
+/// delimited by "::", "cur[rent]" for running task, "last" or
+/// -N for N-th most recent task, or a part of task label,
+/// which matches exactly one task.
+pub(crate) fn select_task<T: DBRoot>(
+  db: &T,
+  s: &str,
+) -> CliResult<Node> {
+  let s = s.trim();
+  let lower = s.to_lowercase();
+
+  if lower == "cur" || lower == "current" {
+    return match db.cur_running()? {
+      Some((node, _)) => Ok(node),
+      None => Err(CliError::Task {
+        source: TaskError::Cmd {
+          message: "No task running.".into(),
+        },
+      }),
+    };
+  }
+  if lower == "last" {
+    return select_recent(db, 1);
+  }
+  if let Ok(id) = s.parse::<i64>() {
+    let out_of_range = || CliError::Parse {
+      message: "Task ID is out of range".into(),
+    };
+    if id < 0 {
+      let offset = id
+        .checked_neg()
+        .and_then(|v| v.try_into().ok())
+        .ok_or_else(out_of_range)?;
+      return select_recent(db, offset);
+    }
+    return select_task_by_id(
+      db,
+      id.try_into().map_err(|_| out_of_range())?,
+    );
+  }
+  if s.contains("::") {
+    let path: Vec<&str> = s.split("::").map(|p| p.trim()).collect();
+    let nodes = db.find_path(&path)?;
+    return match nodes.last() {
+      Some(node) if nodes.len() == path.len() && !node.deleted => {
+        Ok(node.to_owned())
+      }
+      _ => Err(CliError::Cmd {
+        message: format!("There is no task with path \"{}\"", s),
+      }),
+    };
+  }
+
+  let mut matched: Vec<Node> = db
+    .get_by_filter::<Node>(ne(Node::deleted_n(), 1))?
+    .into_iter()
+    .filter(|n| n.label.to_lowercase().contains(&lower))
+    .collect();
+  if matched
+    .iter()
+    .filter(|n| n.label.to_lowercase() == lower)
+    .count()
+    == 1
+  {
+    matched.retain(|n| n.label.to_lowercase() == lower);
+  }
+
+  match matched.len() {
+    0 => Err(CliError::Cmd {
+      message: format!("There is no task matching \"{}\"", s),
+    }),
+    1 => Ok(matched.remove(0)),
+    _ => {
+      let mut candidates = Vec::new();
+      for node in matched {
+        candidates.push(format!(
+          "  {}: {}",
+          node.id,
+          format_task_name(&db.ancestors(node.id)?)
+        ));
+      }
+      Err(CliError::Cmd {
+        message: format!(
+          "Task selector \"{}\" is ambiguous, candidates are:\n{}",
+          s,
+          candidates.join("\n")
+        ),
+      })
+    }
+  }
+}
+
+/// Returns tasks by comma separated selectors.
+pub(crate) fn select_tasks<T: DBRoot>(
+  db: &T,
+  s: &str,
+) -> CliResult<Vec<Node>> {
+  s.split(',')
+    .map(|v| v.trim())
+    .filter(|v| !v.is_empty())
+    .map(|v| select_task(db, v))
+    .collect()
+}
+
+fn select_task_by_id<T: DBRoot>(
+  db: &T,
+  id: usize,
+) -> CliResult<Node> {
+  let node = db.get_by_id::<Node>(id).map_err(|source| {
+    if let DBError::IsEmpty { .. } = source {
+      return CliError::Cmd {
+        message: format!("There is no task with ID {}", id),
+      };
+    }
+
+    CliError::DB { source }
+  })?;
+  if node.deleted {
+    return Err(CliError::Cmd {
+      message: format!("Task {} is deleted", id),
+    });
+  }
+  Ok(node)
+}
+
+/// Returns N-th (starting with 1) distinct task from the most
+/// recent intervals.
+fn select_recent<T: DBRoot>(
+  db: &T,
+  offset: usize,
+) -> CliResult<Node> {
+  let intervals: Vec<Interval> = db.get_by_statement(
+    filter(ne(Interval::deleted_n(), 1))
+      .sort(Interval::begin_n(), SortDir::Descend),
+  )?;
+
+  let mut seen = Vec::new();
+  for interval in intervals {
+    let id = match interval.node_id {
+      Some(id) if !seen.contains(&id) => id,
+      _ => continue,
+    };
+    seen.push(id);
+    if seen.len() == offset {
+      return select_task_by_id(db, id);
+    }
+  }
+
+  Err(CliError::Cmd {
+    message: "There is no task with given offset".into(),
+  })
+}
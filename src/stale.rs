@@ -0,0 +1,115 @@
+use std::io::{stderr, stdin, stdout};
+
+use crossterm::tty::IsTty;
+use crossterm_input::input;
+
+use crate::core::*;
+use crate::parse::*;
+use crate::validate::*;
+use crate::*;
+
+/// Warns, if running interval is longer, than maximum interval
+/// length from config, and offers to stop or to trim it, unless
+/// the command ends it anyway. Messages go to stderr, so they
+/// don't mix with command output.
+pub(crate) fn check_running<T: DBRoot, P: Printer>(
+  ctx: &AppContext<T, P>,
+) -> CliResult<()> {
+  if is_machine_output(&ctx.args) {
+    return Ok(());
+  }
+  let max = match &ctx.conf.max_interval {
+    Some(max) => parse_duration(max)?,
+    None => return Ok(()),
+  };
+  let (node, mut interval) = match ctx.db.cur_running()? {
+    Some(running) => running,
+    None => return Ok(()),
+  };
+
+  let now = Utc::now();
+  if now - interval.begin <= max {
+    return Ok(());
+  }
+
+  let task = ctx.db.ancestors(node.id)?;
+  let printer = ctx.printer.stderr();
+  printer.warning(&format!(
+    "Interval is running for more than {}",
+    format_duration(&max)
+  ));
+  printer.interval_cmd(&IntervalCmdData {
+    cmd_text: "",
+    interval: IntervalData {
+      interval: &interval,
+      task: &task,
+      title: IntervalData::default_title(),
+    },
+  });
+  if ends_running(&ctx.args)
+    || !(stdin().is_tty() && stdout().is_tty() && stderr().is_tty())
+  {
+    return Ok(());
+  }
+
+  let plausible = interval.begin + max;
+  printer.prompt(&format!(
+    "[s] stop it at {}, [t] trim it, [any other key] keep it running",
+    format_datetime(&plausible, &ctx.tz)
+  ));
+  let answer = input()
+    .read_char()
+    .map_err(|e| CliError::wrap(Box::new(e)))?;
+
+  let end = match answer {
+    's' => plausible,
+    't' => {
+      printer.prompt("Enter end date and time or interval length:");
+      let line = input()
+        .read_line()
+        .map_err(|e| CliError::wrap(Box::new(e)))?;
+      let line = line.trim();
+      if let Ok(end) = parse_date_time(line, &ctx.tz) {
+        end
+      } else {
+        interval.begin + parse_duration(line)?
+      }
+    }
+    _ => return Ok(()),
+  };
+
+  interval.end = Some(end);
+  check_interval(ctx.db, &interval)?;
+  ctx.db.save(&interval)?;
+
+  printer.interval_cmd(&IntervalCmdData {
+    cmd_text: "Interval stopped.",
+    interval: IntervalData {
+      interval: &interval,
+      task: &task,
+      title: IntervalData::default_title(),
+    },
+  });
+
+  Ok(())
+}
+
+fn ends_running(args: &ArgMatches) -> bool {
+  matches!(args.subcommand_name(), Some("stop" | "switch" | "cancel"))
+}
+
+/// Checks if command output is meant for other programs, so
+/// nothing else should be printed or asked.
+fn is_machine_output(args: &ArgMatches) -> bool {
+  match args.subcommand() {
+    ("pick", _) => true,
+    ("report", Some(m)) => {
+      m.subcommand()
+        .1
+        .and_then(|m| m.value_of("format"))
+        .map(|f| f != "markdown")
+        == Some(true)
+    }
+    _ => false,
+  }
+}
@@ -241,6 +241,7 @@ impl TaskListStyle {
 pub struct AppStyle {
   pub task: TaskStyle,
   pub error: ContentStyle,
+  pub warning: ContentStyle,
   pub plain: ContentStyle,
   pub report: MadSkin,
   pub task_list: TaskListStyle,
@@ -282,6 +283,10 @@ impl AppStyle {
         foreground_color: Some(colors.c4),
         ..Default::default()
       },
+      warning: ContentStyle {
+        foreground_color: Some(colors.c1),
+        ..Default::default()
+      },
       plain,
       report,
       screen_width: area,
@@ -311,6 +316,7 @@ impl AppStyle {
       task: TaskStyle::empty(),
       task_list: TaskListStyle::empty(),
       error: Default::default(),
+      warning: Default::default(),
       plain: Default::default(),
       report,
       screen_width: Some(4000),
@@ -0,0 +1,89 @@
+use std::fmt;
+
+use chrono::prelude::*;
+use chrono::LocalResult;
+use chrono_tz::Tz;
+
+/// Timezone, dates are parsed and shown in. It's the system one,
+/// unless given by "tz" argument or config.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AppTz {
+  #[default]
+  Local,
+  Fixed(FixedOffset),
+  Zone(Tz),
+}
+
+/// Offset of `AppTz`, which keeps the timezone, so date
+/// arithmetic follows it's daylight saving time rules.
+#[derive(Debug, Clone, Copy)]
+pub struct AppOffset {
+  tz: AppTz,
+  offset: FixedOffset,
+}
+
+impl Offset for AppOffset {
+  fn fix(&self) -> FixedOffset {
+    self.offset
+  }
+}
+
+impl fmt::Display for AppOffset {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    self.offset.fmt(f)
+  }
+}
+
+impl AppTz {
+  fn wrap(&self, offset: FixedOffset) -> AppOffset {
+    AppOffset { tz: *self, offset }
+  }
+}
+
+impl TimeZone for AppTz {
+  type Offset = AppOffset;
+
+  fn from_offset(offset: &AppOffset) -> Self {
+    offset.tz
+  }
+
+  fn offset_from_local_date(
+    &self,
+    local: &NaiveDate,
+  ) -> LocalResult<AppOffset> {
+    match local.and_hms_opt(0, 0, 0) {
+      Some(dt) => self.offset_from_local_datetime(&dt),
+      None => LocalResult::None,
+    }
+  }
+
+  fn offset_from_local_datetime(
+    &self,
+    local: &NaiveDateTime,
+  ) -> LocalResult<AppOffset> {
+    match self {
+      AppTz::Local => Local
+        .offset_from_local_datetime(local)
+        .map(|o| self.wrap(o)),
+      AppTz::Fixed(o) => LocalResult::Single(self.wrap(*o)),
+      AppTz::Zone(tz) => tz
+        .offset_from_local_datetime(local)
+        .map(|o| self.wrap(o.fix())),
+    }
+  }
+
+  fn offset_from_utc_date(&self, utc: &NaiveDate) -> AppOffset {
+    self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+  }
+
+  fn offset_from_utc_datetime(
+    &self,
+    utc: &NaiveDateTime,
+  ) -> AppOffset {
+    self.wrap(match self {
+      AppTz::Local => Local.offset_from_utc_datetime(utc),
+      AppTz::Fixed(o) => *o,
+      AppTz::Zone(tz) => tz.offset_from_utc_datetime(utc).fix(),
+    })
+  }
+}
@@ -0,0 +1,63 @@
+use chrono::prelude::*;
+
+use crate::core::*;
+use crate::errors::*;
+
+/// Checks interval bounds and that it doesn't overlap
+/// with other intervals.
+pub(crate) fn check_interval<T: DBRoot>(
+  db: &T,
+  interval: &Interval,
+) -> CliResult<()> {
+  check_bounds(interval)?;
+  check_overlaps(db, interval)
+}
+
+/// Checks that interval begins before it's end, and
+/// both of them are not in the future.
+pub(crate) fn check_bounds(interval: &Interval) -> CliResult<()> {
+  let now = Utc::now();
+  if interval.begin > now {
+    return Err(CliError::Cmd {
+      message: "Interval can't begin in the future".into(),
+    });
+  }
+  if let Some(end) = interval.end {
+    if interval.begin >= end {
+      return Err(CliError::Cmd {
+        message: "Interval begin should be earlier than it's end"
+          .into(),
+      });
+    }
+    if end > now {
+      return Err(CliError::Cmd {
+        message: "Interval can't end in the future".into(),
+      });
+    }
+  }
+
+  Ok(())
+}
+
+/// Returns error with first of intervals, which overlaps
+/// with the given one.
+pub(crate) fn check_overlaps<T: DBRoot>(
+  db: &T,
+  interval: &Interval,
+) -> CliResult<()> {
+  if let Some(other) = db.overlapping(interval)?.first() {
+    let task = db.ancestors(other.node_id.unwrap())?;
+    return Err(CliError::Task {
+      source: TaskError::CmdTaskInterval {
+        message: format!(
+          "Interval overlaps with interval {}",
+          other.id
+        ),
+        interval: *other,
+        task,
+      },
+    });
+  }
+
+  Ok(())
+}
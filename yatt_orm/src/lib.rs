@@ -197,6 +197,15 @@ impl From<Option<DateTime<Utc>>> for FieldVal {
     }
   }
 }
+impl From<Option<i32>> for FieldVal {
+  fn from(val: Option<i32>) -> FieldVal {
+    if let Some(v) = val {
+      FieldVal::I64(v.into())
+    } else {
+      FieldVal::Null
+    }
+  }
+}
 impl From<Option<usize>> for FieldVal {
   fn from(val: Option<usize>) -> FieldVal {
     if let Some(v) = val {
@@ -222,6 +231,23 @@ impl TryFrom<FieldVal> for usize {
     }
   }
 }
+impl TryFrom<FieldVal> for i32 {
+  type Error = DBError;
+
+  fn try_from(val: FieldVal) -> Result<Self, Self::Error> {
+    match val {
+      FieldVal::I64(v) => {
+        Ok(v.try_into().map_err(|e| DBError::wrap(Box::new(e)))?)
+      }
+      FieldVal::Usize(v) => {
+        Ok(v.try_into().map_err(|e| DBError::wrap(Box::new(e)))?)
+      }
+      _ => Err(DBError::Convert {
+        message: "wrong enum value i32".into(),
+      }),
+    }
+  }
+}
 impl TryFrom<FieldVal> for DateTime<Local> {
   type Error = DBError;
 
@@ -304,6 +330,19 @@ impl TryFrom<FieldVal> for Option<usize> {
     }
   }
 }
+impl TryFrom<FieldVal> for Option<i32> {
+  type Error = DBError;
+
+  fn try_from(val: FieldVal) -> Result<Self, Self::Error> {
+    if let FieldVal::Null = val {
+      Ok(None)
+    } else {
+      Ok(Some(
+        val.try_into().map_err(|e| DBError::wrap(Box::new(e)))?,
+      ))
+    }
+  }
+}
 impl TryFrom<FieldVal> for Option<String> {
   type Error = DBError;
 